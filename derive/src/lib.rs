@@ -0,0 +1,326 @@
+//! Procedural macros for `json-rpc-types`
+
+#![warn(missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span};
+use quote::quote;
+
+///Describes trait's method
+struct RpcMethod {
+    ///Method's identifier, which is also JSON-RPC method's name.
+    ident: Ident,
+    ///Identifier of method's type, implementing `Method`.
+    type_ident: Ident,
+    ///Types of arguments.
+    args: Vec<syn::Type>,
+    ///Type of successful result.
+    result: syn::Type,
+    ///Type arguments of `json_rpc_types::Error`, which are data and optionally message.
+    error: Vec<syn::Type>,
+    ///Whether method returns `Result`.
+    is_fallible: bool,
+}
+
+fn to_camel_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for part in text.split('_').filter(|part| !part.is_empty()) {
+        let mut chars = part.chars();
+        if let Some(ch) = chars.next() {
+            result.extend(ch.to_uppercase());
+            result.push_str(chars.as_str());
+        }
+    }
+    result
+}
+
+///Converts camel case to snake case, treating run of capitals as single word (e.g. `HTTPApi` becomes `http_api`).
+fn to_snake_case(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len() + 4);
+    for (idx, ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() {
+            if idx != 0 {
+                let prev = chars[idx - 1];
+                let is_next_lower = chars.get(idx + 1).is_some_and(|next| next.is_lowercase());
+                if !prev.is_uppercase() || is_next_lower {
+                    result.push('_');
+                }
+            }
+            result.extend(ch.to_lowercase());
+        } else {
+            result.push(*ch);
+        }
+    }
+    result
+}
+
+///Returns type arguments of path's last segment, if its name is `name`.
+fn type_args<'a>(ty: &'a syn::Type, name: &str) -> Option<Vec<&'a syn::Type>> {
+    let path = match ty {
+        syn::Type::Path(path) => path,
+        _ => return None,
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => Some(args.args.iter().filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }).collect()),
+        _ => Some(Vec::new()),
+    }
+}
+
+fn parse_method(method: &syn::TraitItemFn) -> Result<RpcMethod, syn::Error> {
+    let sig = &method.sig;
+    match sig.receiver() {
+        Some(receiver) if receiver.mutability.is_none() && matches!(receiver.kind, syn::ReceiverKind::Reference(_, _, None)) => (),
+        Some(receiver) => return Err(syn::Error::new_spanned(receiver, "RPC method must accept `&self`")),
+        None => return Err(syn::Error::new_spanned(sig, "RPC method must accept `&self`")),
+    }
+    if !sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&sig.generics, "RPC method cannot be generic"));
+    }
+
+    let mut args = Vec::new();
+    for arg in sig.inputs.iter() {
+        if let syn::FnArg::Typed(arg) = arg {
+            args.push((*arg.ty).clone());
+        }
+    }
+
+    let unit: syn::Type = syn::parse_quote!(());
+    let (result, error, is_fallible) = match &sig.output {
+        syn::ReturnType::Default => (unit.clone(), vec![unit], false),
+        syn::ReturnType::Type(_, ty) => match type_args(ty, "Result") {
+            Some(result) if result.len() == 2 => match type_args(result[1], "Error") {
+                Some(error) if error.is_empty() => (result[0].clone(), vec![unit], true),
+                Some(error) => (result[0].clone(), error.into_iter().cloned().collect(), true),
+                None => return Err(syn::Error::new_spanned(result[1], "RPC method's error must be `json_rpc_types::Error`")),
+            },
+            _ => ((**ty).clone(), vec![unit], false),
+        },
+    };
+
+    Ok(RpcMethod {
+        ident: sig.ident.clone(),
+        type_ident: Ident::new(&to_camel_case(&sig.ident.to_string()), sig.ident.span()),
+        args,
+        result,
+        error,
+        is_fallible,
+    })
+}
+
+fn expand(input: syn::ItemTrait) -> Result<proc_macro2::TokenStream, syn::Error> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "RPC trait cannot be generic"));
+    }
+
+    let mut methods = Vec::new();
+    for item in input.items.iter() {
+        match item {
+            syn::TraitItem::Fn(method) => methods.push(parse_method(method)?),
+            item => return Err(syn::Error::new_spanned(item, "RPC trait can contain only methods")),
+        }
+    }
+
+    let vis = &input.vis;
+    let trait_ident = &input.ident;
+    let mod_ident = Ident::new(&to_snake_case(&trait_ident.to_string()), Span::call_site());
+    let mod_doc = format!("Generated JSON-RPC definitions of `{}`", trait_ident);
+
+    //Types are aliased outside of generated module, so that they are resolved within trait's scope.
+    let mut type_aliases = Vec::new();
+    let mut method_types = Vec::new();
+    let mut request_variants = Vec::new();
+    let mut response_variants = Vec::new();
+    let mut response_serialize = Vec::new();
+    let mut dispatch_arms = Vec::new();
+
+    for method in methods.iter() {
+        let RpcMethod { ident, type_ident, args, result, error, is_fallible } = method;
+        let name = ident.to_string();
+        let doc = format!("Definition of `{}` method", name);
+        let arg_idents: Vec<_> = (0..args.len()).map(|idx| Ident::new(&format!("arg{}", idx), Span::call_site())).collect();
+        let alias = |kind: &str| Ident::new(&format!("__{}{}{}", trait_ident, type_ident, kind), Span::call_site());
+        let params_alias = alias("Params");
+        let result_alias = alias("Result");
+        let error_aliases: Vec<_> = ["Error", "ErrorMessage"].iter().take(error.len()).map(|kind| alias(kind)).collect();
+        //Empty array is used instead of unit, to serialize as empty positional params.
+        let params_type = match args.len() {
+            0 => quote!([(); 0]),
+            _ => quote!((#(#args,)*)),
+        };
+        type_aliases.push(quote! {
+            #[doc(hidden)]
+            type #params_alias = #params_type;
+            #[doc(hidden)]
+            type #result_alias = #result;
+            #(
+                #[doc(hidden)]
+                type #error_aliases = #error;
+            )*
+        });
+        let params = quote!(super::#params_alias);
+        let result = quote!(super::#result_alias);
+        let error_data = &error_aliases[0];
+        let error = quote!(#(super::#error_aliases),*);
+        let payload = match is_fallible {
+            true => quote!(server.#ident(#(#arg_idents),*)),
+            false => quote!(core::result::Result::Ok(server.#ident(#(#arg_idents),*))),
+        };
+
+        method_types.push(quote! {
+            #[doc = #doc]
+            pub struct #type_ident;
+
+            impl json_rpc_types::Method for #type_ident {
+                const NAME: &'static str = #name;
+                type Params = #params;
+                type Result = #result;
+                type Error = super::#error_data;
+            }
+        });
+
+        match args.len() {
+            0 => {
+                request_variants.push(quote! {
+                    #[serde(rename = #name)]
+                    #type_ident
+                });
+                dispatch_arms.push(quote! {
+                    Some(Request::#type_ident) => {
+                        let payload = #payload;
+                        id.map(|id| Response::#type_ident(json_rpc_types::Response { jsonrpc, payload, id: Some(id) }))
+                    }
+                });
+            },
+            _ => {
+                request_variants.push(quote! {
+                    #[serde(rename = #name)]
+                    #type_ident(#params)
+                });
+                dispatch_arms.push(quote! {
+                    Some(Request::#type_ident((#(#arg_idents,)*))) => {
+                        let payload = #payload;
+                        id.map(|id| Response::#type_ident(json_rpc_types::Response { jsonrpc, payload, id: Some(id) }))
+                    }
+                });
+            },
+        }
+
+        response_variants.push(quote! {
+            #[doc = #doc]
+            #type_ident(json_rpc_types::Response<#result, #error>)
+        });
+        response_serialize.push(quote! {
+            Response::#type_ident(response) => json_rpc_types::serde::Serialize::serialize(response, ser)
+        });
+    }
+
+    Ok(quote! {
+        #input
+
+        #(#type_aliases)*
+
+        #[doc = #mod_doc]
+        #vis mod #mod_ident {
+            #(#method_types)*
+
+            ///Params of any method, to be deserialized as `json_rpc_types::Dispatch<Request>`.
+            #[derive(json_rpc_types::serde_derive::Deserialize)]
+            #[serde(crate = "json_rpc_types::serde")]
+            pub enum Request {
+                #(#request_variants,)*
+            }
+
+            ///Response to any method.
+            pub enum Response {
+                #(#response_variants,)*
+                ///Response to unknown method.
+                NotFound(json_rpc_types::Response<(), ()>),
+                ///Response to request of known method, but with invalid params.
+                InvalidParams(json_rpc_types::Response<(), ()>),
+            }
+
+            impl json_rpc_types::serde::Serialize for Response {
+                fn serialize<S: json_rpc_types::serde::Serializer>(&self, ser: S) -> core::result::Result<S::Ok, S::Error> {
+                    match self {
+                        #(#response_serialize,)*
+                        Response::NotFound(response) => json_rpc_types::serde::Serialize::serialize(response, ser),
+                        Response::InvalidParams(response) => json_rpc_types::serde::Serialize::serialize(response, ser),
+                    }
+                }
+            }
+
+            ///Invokes `server`'s method according to `request`.
+            ///
+            ///Returns `None` if request is notification.
+            pub fn dispatch<S: super::#trait_ident + ?Sized>(server: &S, request: json_rpc_types::Dispatch<Request>) -> Option<Response> {
+                match request {
+                    json_rpc_types::Dispatch::Method(request) => {
+                        let jsonrpc = request.jsonrpc;
+                        let id = request.id;
+                        match request.params {
+                            #(#dispatch_arms,)*
+                            None => id.map(|id| {
+                                let error = json_rpc_types::Error::from_code(json_rpc_types::ErrorCode::InvalidParams);
+                                Response::InvalidParams(json_rpc_types::Response::error(jsonrpc, error, Some(id)))
+                            }),
+                        }
+                    },
+                    json_rpc_types::Dispatch::InvalidParams(request) => {
+                        let jsonrpc = request.jsonrpc;
+                        request.id.map(|id| {
+                            let error = json_rpc_types::Error::from_code(json_rpc_types::ErrorCode::InvalidParams);
+                            Response::InvalidParams(json_rpc_types::Response::error(jsonrpc, error, Some(id)))
+                        })
+                    },
+                    json_rpc_types::Dispatch::NotFound(request) => {
+                        let jsonrpc = request.jsonrpc;
+                        request.id.map(|id| {
+                            let error = json_rpc_types::Error::from_code(json_rpc_types::ErrorCode::MethodNotFound);
+                            Response::NotFound(json_rpc_types::Response::error(jsonrpc, error, Some(id)))
+                        })
+                    },
+                }
+            }
+        }
+    })
+}
+
+///Generates JSON-RPC definitions out of trait.
+///
+///Each method must accept `&self` and return either plain value or `Result<T, json_rpc_types::Error<E, M>>`.
+///Arguments are passed as positional params.
+///
+///Generated module, named after trait in snake case (run of capitals is treated as single word, e.g. `HTTPApi` becomes `http_api`), contains:
+///
+///- Per method type implementing `Method`, named after method in camel case, for use by client;
+///- `Request` - enum of methods' params, to be deserialized as `Dispatch<Request>`;
+///- `Response` - response of any method;
+///- `dispatch` - function which invokes server's method according to request.
+///
+///Types of methods are resolved within trait's scope via hidden type aliases, placed next to trait.
+///
+///Client side is limited to per method types, which build requests and decode responses, while
+///transport and correlation of responses to requests are out of scope.
+///Note that `Method` uses default error message, therefore message type of `Error<E, M>` applies to server's `Response` only.
+#[proc_macro_attribute]
+pub fn rpc(args: TokenStream, input: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        let args = proc_macro2::TokenStream::from(args);
+        return syn::Error::new_spanned(args, "`rpc` doesn't accept arguments").to_compile_error().into();
+    }
+
+    let input = syn::parse_macro_input!(input as syn::ItemTrait);
+    match expand(input) {
+        Ok(output) => output.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
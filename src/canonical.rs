@@ -0,0 +1,301 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::ser::SerializeMap;
+
+use crate::version::Version;
+use crate::id::Id;
+use crate::request::Request;
+use crate::response::Response;
+use crate::message::Message;
+
+use core::marker::PhantomData;
+
+///Member of message, which position is fixed by `CanonicalPolicy`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Member {
+    ///`jsonrpc` or `version` member.
+    Version,
+    ///`id` member.
+    Id,
+    ///`method` member of request.
+    Method,
+    ///`params` member of request.
+    Params,
+    ///`result` or `error` member of response.
+    ///
+    ///JSON-RPCv1 response contains both, with `result` going first.
+    Payload,
+}
+
+///Describes canonical form of messages.
+///
+///Policy is validated at compile time, once message is serialized with it.
+pub trait CanonicalPolicy {
+    ///Order of request's members.
+    ///
+    ///Must contain `Version`, `Id`, `Method` and `Params` exactly once.
+    const REQUEST: [Member; 4];
+    ///Order of response's members.
+    ///
+    ///Must contain `Version`, `Id` and `Payload` exactly once.
+    const RESPONSE: [Member; 3];
+    ///Whether string `id`, containing decimal number which fits `u64`, is serialized as number.
+    const NUMERIC_ID: bool = false;
+    ///Whether keys of objects within `params` are sorted.
+    ///
+    ///Requires `serde_json` feature, otherwise compilation fails.
+    const SORT_PARAMS: bool = false;
+}
+
+///Returns whether `members` contain each of `expected` exactly once and nothing else.
+const fn is_permutation(members: &[Member], expected: &[Member]) -> bool {
+    if members.len() != expected.len() {
+        return false;
+    }
+
+    let mut idx = 0;
+    while idx < expected.len() {
+        let mut count = 0;
+        let mut pos = 0;
+        while pos < members.len() {
+            if members[pos] as u8 == expected[idx] as u8 {
+                count += 1;
+            }
+            pos += 1;
+        }
+        if count != 1 {
+            return false;
+        }
+        idx += 1;
+    }
+
+    true
+}
+
+///Compile time validation of policy `C`.
+struct Validate<C>(PhantomData<C>);
+
+impl<C: CanonicalPolicy> Validate<C> {
+    const CHECK: () = {
+        assert!(is_permutation(&C::REQUEST, &[Member::Version, Member::Id, Member::Method, Member::Params]), "CanonicalPolicy::REQUEST must contain each request member exactly once");
+        assert!(is_permutation(&C::RESPONSE, &[Member::Version, Member::Id, Member::Payload]), "CanonicalPolicy::RESPONSE must contain each response member exactly once");
+        assert!(cfg!(feature = "serde_json") || !C::SORT_PARAMS, "CanonicalPolicy::SORT_PARAMS requires serde_json feature");
+    };
+}
+
+///Policy, placing `id` right after `jsonrpc`.
+///
+///Request is serialized as `jsonrpc`, `id`, `method`, `params`, while response as `jsonrpc`, `id`, `result` or `error`.
+pub struct IdFirst;
+
+impl CanonicalPolicy for IdFirst {
+    const REQUEST: [Member; 4] = [Member::Version, Member::Id, Member::Method, Member::Params];
+    const RESPONSE: [Member; 3] = [Member::Version, Member::Id, Member::Payload];
+}
+
+///Policy, following order of members in specification.
+///
+///Request is serialized as `jsonrpc`, `method`, `params`, `id`, while response as `jsonrpc`, `result` or `error`, `id`.
+pub struct SpecOrder;
+
+impl CanonicalPolicy for SpecOrder {
+    const REQUEST: [Member; 4] = [Member::Version, Member::Method, Member::Params, Member::Id];
+    const RESPONSE: [Member; 3] = [Member::Version, Member::Payload, Member::Id];
+}
+
+///Wrapper to serialize message in deterministic canonical form, described by policy `C`.
+///
+///Produces byte identical output for equal messages, which makes it suitable for signing and caching:
+///
+///- Members are serialized in order specified by policy, regardless of protocol version;
+///- Omitted members are the same as for inner type, e.g. JSON-RPCv1 request always contains `params` and `id`;
+///- `id` is normalized according to policy.
+///
+///```rust
+///use json_rpc_types::{Canonical, Request};
+///
+///let request = Request::<[u8; 1], &str>::new("ping").set_params([1]).set_id(1);
+///let json = serde_json::to_string(&Canonical::<_>::new(&request)).unwrap();
+///assert_eq!(json, r#"{"jsonrpc":"2.0","id":1,"method":"ping","params":[1]}"#);
+///```
+///
+///Deserialization is the same as for inner type.
+pub struct Canonical<T, C=IdFirst>(pub T, PhantomData<C>);
+
+impl_wrapper!(Canonical<C>);
+
+impl<'de, T: Deserialize<'de>, C> Deserialize<'de> for Canonical<T, C> {
+    #[inline(always)]
+    fn deserialize<D: Deserializer<'de>>(des: D) -> Result<Self, D::Error> {
+        T::deserialize(des).map(Self::new)
+    }
+}
+
+///`id` normalized according to policy.
+struct CanonicalId<'a, C>(&'a Id, PhantomData<C>);
+
+impl<C: CanonicalPolicy> Serialize for CanonicalId<'_, C> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Id::Num(id) => ser.serialize_u64(*id),
+            Id::Str(id) => match C::NUMERIC_ID {
+                true => match id.as_str().parse::<u64>() {
+                    Ok(id) => ser.serialize_u64(id),
+                    Err(_) => ser.serialize_str(id.as_str()),
+                },
+                false => ser.serialize_str(id.as_str()),
+            },
+        }
+    }
+}
+
+#[inline]
+fn serialize_id<M: SerializeMap, C: CanonicalPolicy>(state: &mut M, id: &Option<Id>) -> Result<(), M::Error> {
+    match id {
+        Some(id) => state.serialize_entry("id", &CanonicalId::<C>(id, PhantomData)),
+        None => state.serialize_entry("id", &()),
+    }
+}
+
+#[inline]
+fn serialize_version<M: SerializeMap>(state: &mut M, version: Version) -> Result<(), M::Error> {
+    match version {
+        Version::V1 => Ok(()),
+        Version::V1_1 => state.serialize_entry("version", &version),
+        Version::V2 => state.serialize_entry("jsonrpc", &version),
+    }
+}
+
+///`params` with keys of objects sorted according to policy.
+struct CanonicalParams<'a, P, C>(&'a P, PhantomData<C>);
+
+impl<P: Serialize, C: CanonicalPolicy> Serialize for CanonicalParams<'_, P, C> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        if !C::SORT_PARAMS {
+            return self.0.serialize(ser);
+        }
+
+        #[cfg(feature = "serde_json")]
+        {
+            let mut params = serde_json::to_value(self.0).map_err(serde::ser::Error::custom)?;
+            params.sort_all_objects();
+            params.serialize(ser)
+        }
+
+        //Sorting without `serde_json` is rejected by `Validate`.
+        #[cfg(not(feature = "serde_json"))]
+        {
+            self.0.serialize(ser)
+        }
+    }
+}
+
+fn serialize_request<S: Serializer, P: Serialize, T: Serialize, C: CanonicalPolicy>(request: &Request<P, T>, ser: S) -> Result<S::Ok, S::Error> {
+    let () = Validate::<C>::CHECK;
+
+    let is_v1 = request.jsonrpc == Version::V1;
+    let mut len = 0;
+    for member in C::REQUEST.iter() {
+        len += match member {
+            Member::Version => !is_v1 as usize,
+            Member::Id => (is_v1 || request.id.is_some()) as usize,
+            Member::Method => 1,
+            Member::Params => (is_v1 || request.params.is_some()) as usize,
+            Member::Payload => 0,
+        };
+    }
+
+    let mut state = ser.serialize_map(Some(len))?;
+    for member in C::REQUEST.iter() {
+        match member {
+            Member::Version => serialize_version(&mut state, request.jsonrpc)?,
+            Member::Id => if is_v1 || request.id.is_some() {
+                serialize_id::<_, C>(&mut state, &request.id)?;
+            },
+            Member::Method => state.serialize_entry("method", &request.method)?,
+            Member::Params => match request.params {
+                Some(ref params) => state.serialize_entry("params", &CanonicalParams::<_, C>(params, PhantomData))?,
+                None if is_v1 => state.serialize_entry("params", &[(); 0])?,
+                None => (),
+            },
+            Member::Payload => (),
+        }
+    }
+    state.end()
+}
+
+fn serialize_response<S: Serializer, R: Serialize, E: Serialize, EM: Serialize, C: CanonicalPolicy>(response: &Response<R, E, EM>, ser: S) -> Result<S::Ok, S::Error> {
+    let () = Validate::<C>::CHECK;
+
+    let is_v1 = response.jsonrpc == Version::V1;
+    let mut len = 0;
+    for member in C::RESPONSE.iter() {
+        len += match member {
+            Member::Version => !is_v1 as usize,
+            Member::Id => 1,
+            Member::Payload => 1 + is_v1 as usize,
+            Member::Method | Member::Params => 0,
+        };
+    }
+
+    let mut state = ser.serialize_map(Some(len))?;
+    for member in C::RESPONSE.iter() {
+        match member {
+            Member::Version => serialize_version(&mut state, response.jsonrpc)?,
+            Member::Id => serialize_id::<_, C>(&mut state, &response.id)?,
+            Member::Payload => match (&response.payload, is_v1) {
+                (Ok(result), false) => state.serialize_entry("result", result)?,
+                (Err(error), false) => state.serialize_entry("error", error)?,
+                (Ok(result), true) => {
+                    state.serialize_entry("result", result)?;
+                    state.serialize_entry("error", &())?;
+                },
+                (Err(error), true) => {
+                    state.serialize_entry("result", &())?;
+                    state.serialize_entry("error", error)?;
+                },
+            },
+            Member::Method | Member::Params => (),
+        }
+    }
+    state.end()
+}
+
+impl<P: Serialize, T: Serialize, C: CanonicalPolicy> Serialize for Canonical<Request<P, T>, C> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        serialize_request::<_, _, _, C>(&self.0, ser)
+    }
+}
+
+impl<P: Serialize, T: Serialize, C: CanonicalPolicy> Serialize for Canonical<&Request<P, T>, C> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        serialize_request::<_, _, _, C>(self.0, ser)
+    }
+}
+
+impl<R: Serialize, E: Serialize, EM: Serialize, C: CanonicalPolicy> Serialize for Canonical<Response<R, E, EM>, C> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        serialize_response::<_, _, _, _, C>(&self.0, ser)
+    }
+}
+
+impl<R: Serialize, E: Serialize, EM: Serialize, C: CanonicalPolicy> Serialize for Canonical<&Response<R, E, EM>, C> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        serialize_response::<_, _, _, _, C>(self.0, ser)
+    }
+}
+
+impl<P: Serialize, R: Serialize, E: Serialize, T: Serialize, EM: Serialize, C: CanonicalPolicy> Serialize for Canonical<Message<P, R, E, T, EM>, C> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match &self.0 {
+            Message::Request(request) => serialize_request::<_, _, _, C>(request, ser),
+            Message::Response(response) => serialize_response::<_, _, _, _, C>(response, ser),
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use serde::{Deserialize, Deserializer};
+use serde::de::{self, IgnoredAny, MapAccess, Visitor};
+
+use crate::version::Version;
+use crate::id::{Id, IdOnly};
+use crate::error::{Error, ErrorCode};
+use crate::request::Request;
+use crate::dispatch::Dispatch;
+use crate::response::Response;
+use crate::utils::Structured;
+
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+
+type Data = str_buf::StrBuf<128>;
+
+///Rejected request, describing error to reply with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rejected {
+    ///Request's version, or `V2` if it could not be recovered.
+    pub jsonrpc: Version,
+    ///Error with `data` containing path to invalid member, followed by description of failure.
+    ///
+    ///Description is truncated, if it exceeds capacity.
+    pub error: Error<Data>,
+    ///Request's `id`, if it could be recovered.
+    pub id: Option<Id>,
+    path: &'static str,
+    has_id: bool,
+}
+
+impl Rejected {
+    fn new(jsonrpc: Version, code: ErrorCode, path: &'static str, id: Option<Id>, has_id: bool, reason: &dyn fmt::Display) -> Self {
+        let mut data = Data::new();
+        //Overflow only truncates description
+        let _ = match path {
+            "" => write!(data, "{}", reason),
+            path => write!(data, "{}: {}", path, reason),
+        };
+        let mut error = Error::from_code(code);
+        error.data = Some(data);
+        Self {
+            jsonrpc,
+            error,
+            id,
+            path,
+            has_id,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns path to invalid member, or empty string if whole message is invalid.
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    #[inline(always)]
+    ///Returns whether request contains `id` member, which might be `null` or invalid.
+    pub fn has_id(&self) -> bool {
+        self.has_id
+    }
+
+    #[inline(always)]
+    ///Returns whether request is notification, i.e. `id` member is absent.
+    ///
+    ///Specification requires no reply to notification, unless it is `ParseError` or `InvalidRequest`.
+    pub fn is_notification(&self) -> bool {
+        !self.has_id
+    }
+
+    #[inline]
+    ///Creates error response of request's version.
+    pub fn into_response<R>(self) -> Response<R, Data> {
+        Response::error(self.jsonrpc, self.error, self.id)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+///Parses JSON request, classifying failure according to specification:
+///
+///- `ParseError` - Input is not valid JSON;
+///- `InvalidRequest` - Input is not valid request, e.g. not an object, invalid `method` or `params` is neither array, object nor `null`;
+///- `InvalidParams` - `params` cannot be deserialized as `P`.
+///
+///Parsing is performed in stages, so input might be parsed multiple times in case of failure.
+pub fn parse_request<'de, P: Deserialize<'de>, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<Request<P, T>, Rejected> {
+    parse::<_, T>(bytes)
+}
+
+#[allow(clippy::result_large_err)]
+///Parses JSON request, dispatching it to enum of methods `M`, and classifies failure the same as `parse_request`.
+///
+///Except `params`, that cannot be deserialized as `M`, result in `Dispatch::InvalidParams` instead of `Rejected`.
+pub fn parse_dispatch<'de, M: Deserialize<'de>, T: Deserialize<'de> + AsRef<str>>(bytes: &'de [u8]) -> Result<Dispatch<M, T>, Rejected> {
+    match parse::<_, T>(bytes) {
+        Err(rejected) if rejected.error.code == ErrorCode::InvalidParams => match serde_json::from_slice::<Request<IgnoredAny, T>>(bytes) {
+            Ok(request) => Ok(Dispatch::InvalidParams(Request {
+                jsonrpc: request.jsonrpc,
+                method: request.method,
+                params: None,
+                id: request.id,
+            })),
+            Err(_) => Err(rejected),
+        },
+        result => result,
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn parse<'de, V: Deserialize<'de>, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<V, Rejected> {
+    if let Err(error) = serde_json::from_slice::<IgnoredAny>(bytes) {
+        return Err(Rejected::new(Version::V2, ErrorCode::ParseError, "", None, false, &error));
+    }
+
+    let mut envelope = Envelope::<T> {
+        jsonrpc: Version::V2,
+        path: "",
+        id: None,
+        _method: PhantomData,
+    };
+    let mut der = serde_json::Deserializer::from_slice(bytes);
+    if let Err(error) = (&mut der).deserialize_any(&mut envelope) {
+        let (id, has_id) = match envelope.id.take() {
+            Some(id) => (id, true),
+            None => (recover_id(bytes), has_id(bytes)),
+        };
+        return Err(Rejected::new(envelope.jsonrpc, ErrorCode::InvalidRequest, envelope.path, id, has_id, &error));
+    }
+
+    match serde_json::from_slice::<V>(bytes) {
+        Ok(request) => Ok(request),
+        Err(error) => {
+            let has_id = envelope.id.is_some();
+            Err(Rejected::new(envelope.jsonrpc, ErrorCode::InvalidParams, "params", envelope.id.unwrap_or(None), has_id, &error))
+        },
+    }
+}
+
+enum Key {
+    JsonRpc,
+    Version,
+    Method,
+    Params,
+    Id,
+    Unknown,
+}
+
+impl<'de> Deserialize<'de> for Key {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(des: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'de> Visitor<'de> for KeyVisitor {
+            type Value = Key;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Key must be a string")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+                match text {
+                    "jsonrpc" => Ok(Key::JsonRpc),
+                    "version" => Ok(Key::Version),
+                    "method" => Ok(Key::Method),
+                    "params" => Ok(Key::Params),
+                    "id" => Ok(Key::Id),
+                    _ => Ok(Key::Unknown),
+                }
+            }
+        }
+
+        des.deserialize_identifier(KeyVisitor)
+    }
+}
+
+///Recovers `id` of JSON message, ignoring any other member.
+///
+///Returns `None` if input is not valid JSON object or `id` is absent or invalid.
+pub fn recover_id(bytes: &[u8]) -> Option<Id> {
+    serde_json::from_slice::<IdOnly>(bytes).ok().and_then(IdOnly::into_inner)
+}
+
+///Checks whether JSON object contains `id` member, regardless of its value.
+fn has_id(bytes: &[u8]) -> bool {
+    struct IdVisitor;
+
+    impl<'de> Visitor<'de> for IdVisitor {
+        type Value = bool;
+
+        #[inline]
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("Object resembling JSON-RPC message")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut has_id = false;
+            while let Some(key) = map.next_key::<Key>()? {
+                has_id |= matches!(key, Key::Id);
+                map.next_value::<IgnoredAny>()?;
+            }
+
+            Ok(has_id)
+        }
+    }
+
+    let mut der = serde_json::Deserializer::from_slice(bytes);
+    der.deserialize_any(IdVisitor).unwrap_or(false)
+}
+
+///Validates request's members, except `params` content, remembering member being validated.
+struct Envelope<T> {
+    jsonrpc: Version,
+    path: &'static str,
+    //`Some(None)` indicates `null` id.
+    id: Option<Option<Id>>,
+    _method: PhantomData<T>,
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for &mut Envelope<T> {
+    type Value = ();
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Object resembling JSON-RPC request type")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut has_version = false;
+        let mut has_method = false;
+        let mut has_params = false;
+
+        while let Some(key) = map.next_key::<Key>()? {
+            match key {
+                Key::Version => {
+                    self.path = "version";
+                    return Err(crate::utils::legacy_version());
+                },
+                Key::JsonRpc => {
+                    self.path = "jsonrpc";
+                    if has_version {
+                        return Err(de::Error::duplicate_field(self.path));
+                    }
+                    self.jsonrpc = map.next_value::<Version>()?;
+                    has_version = true;
+                },
+                Key::Method => {
+                    self.path = "method";
+                    if has_method {
+                        return Err(de::Error::duplicate_field(self.path));
+                    }
+                    map.next_value::<T>()?;
+                    has_method = true;
+                },
+                Key::Params => {
+                    self.path = "params";
+                    if has_params {
+                        return Err(de::Error::duplicate_field(self.path));
+                    }
+                    //`null` is treated as absent `params`, the same as by `Request`.
+                    map.next_value::<Option<Structured<IgnoredAny>>>()?;
+                    has_params = true;
+                },
+                Key::Id => {
+                    self.path = "id";
+                    if self.id.is_some() {
+                        return Err(de::Error::duplicate_field(self.path));
+                    }
+                    self.id = Some(map.next_value::<Option<Id>>()?);
+                },
+                Key::Unknown => {
+                    self.path = "";
+                    return Err(de::Error::custom("JSON-RPC Request contains unknown field"));
+                },
+            }
+        }
+
+        match has_method {
+            true => Ok(()),
+            false => {
+                self.path = "method";
+                Err(de::Error::missing_field("method"))
+            },
+        }
+    }
+}
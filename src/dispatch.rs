@@ -0,0 +1,371 @@
+use serde::{Deserialize, Deserializer};
+use serde::de::{self, DeserializeSeed, EnumAccess, IgnoredAny, MapAccess, VariantAccess, Visitor};
+use serde::de::value::{StrDeserializer, UnitDeserializer};
+
+use crate::version::Version;
+use crate::id::Id;
+use crate::error::{Error, ErrorCode};
+use crate::request::{self, Request, Key};
+use crate::response::Response;
+use crate::ext::Members;
+
+use core::fmt;
+use core::marker::PhantomData;
+
+///Request, which is dispatched to enum of methods `M` by its `method` member.
+///
+///`M` must be enum with regular (externally tagged) representation, which is matched as following:
+///
+///- Variant is selected by name, using `method` member;
+///- Variant's payload is deserialized from `params` member;
+///- Unit variant ignores `params`;
+///- When `params` is absent, payload is deserialized from unit (e.g. `Option` becomes `None`).
+///
+///If `method` is not among variants of `M`, request is deserialized as `NotFound`, which should be
+///replied with `MethodNotFound` error.
+///
+///If payload cannot be deserialized from absent or buffered `params`, request is deserialized as `InvalidParams`,
+///which should be replied with `InvalidParams` error. Otherwise failure is reported by deserializer,
+///as it cannot proceed past invalid value, but `parse_dispatch` recovers it as `InvalidParams` too.
+///
+///Variants are known by names, that `M` passes to `Deserializer::deserialize_enum`.
+///Therefore `#[serde(alias)]` is recognized only if `M` lists aliases among variants, which is
+///the case for recent versions of `serde_derive`.
+///
+///When `params` precede `method`, payload is buffered as `serde_json::Value` until method is known,
+///which requires `serde_json` feature. Without it `method` must precede `params`, otherwise deserialization fails.
+///
+///```rust
+///use json_rpc_types::Dispatch;
+///use serde_derive::Deserialize;
+///
+///#[derive(Deserialize, Debug, PartialEq)]
+///enum Methods {
+///    #[serde(rename = "subtract")]
+///    Subtract([i64; 2]),
+///    #[serde(rename = "ping")]
+///    Ping,
+///}
+///
+///let request: Dispatch<Methods> = serde_json::from_str(r#"{"jsonrpc":"2.0","method":"subtract","params":[42,23],"id":1}"#).unwrap();
+///match request {
+///    Dispatch::Method(request) => assert_eq!(request.params, Some(Methods::Subtract([42, 23]))),
+///    Dispatch::InvalidParams(_) | Dispatch::NotFound(_) => unreachable!(),
+///}
+///```
+#[derive(Clone, Debug, PartialEq)]
+pub enum Dispatch<M, T=request::StrBuf> {
+    ///Request with method known to `M`.
+    ///
+    ///`params` is always `Some`.
+    Method(Request<M, T>),
+    ///Request with known method, whose `params` are invalid and discarded.
+    InvalidParams(Request<(), T>),
+    ///Request with unknown method, whose `params` are discarded.
+    NotFound(Request<(), T>),
+}
+
+impl<M, T> Dispatch<M, T> {
+    ///Returns request of known method or `InvalidParams` or `MethodNotFound` error response.
+    ///
+    ///In case of notification, there should be no response, therefore error is `None`.
+    pub fn into_request<R, E>(self) -> Result<Request<M, T>, Option<Response<R, E>>> {
+        let (request, code) = match self {
+            Dispatch::Method(request) => return Ok(request),
+            Dispatch::InvalidParams(request) => (request, ErrorCode::InvalidParams),
+            Dispatch::NotFound(request) => (request, ErrorCode::MethodNotFound),
+        };
+
+        match request.id {
+            Some(id) => Err(Some(Response::error(request.jsonrpc, Error::from_code(code), Some(id)))),
+            None => Err(None),
+        }
+    }
+}
+
+///Error of `VariantsProbe`, which is never exposed to user.
+#[derive(Debug)]
+struct ProbeError;
+
+impl fmt::Display for ProbeError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("probe")
+    }
+}
+
+impl de::StdError for ProbeError {}
+
+impl de::Error for ProbeError {
+    #[inline(always)]
+    fn custom<T: fmt::Display>(_: T) -> Self {
+        ProbeError
+    }
+}
+
+///Deserializer, which extracts names of enum's variants without deserializing anything.
+struct VariantsProbe<'a>(&'a mut &'static [&'static str]);
+
+impl<'de, 'a> Deserializer<'de> for VariantsProbe<'a> {
+    type Error = ProbeError;
+
+    #[inline(always)]
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(ProbeError)
+    }
+
+    #[inline(always)]
+    fn deserialize_enum<V: Visitor<'de>>(self, _: &'static str, variants: &'static [&'static str], _: V) -> Result<V::Value, Self::Error> {
+        *self.0 = variants;
+        Err(ProbeError)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+fn variants<'de, M: Deserialize<'de>>() -> &'static [&'static str] {
+    let mut variants: &'static [&'static str] = &[];
+    let _ = M::deserialize(VariantsProbe(&mut variants));
+    variants
+}
+
+///Deserializer of `M`, which presents `method` and `params` as enum.
+struct MethodDeserializer<'a, D> {
+    method: &'a str,
+    params: D,
+}
+
+impl<'de, 'a, D: Deserializer<'de>> Deserializer<'de> for MethodDeserializer<'a, D> {
+    type Error = D::Error;
+
+    #[inline(always)]
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a, D: Deserializer<'de>> EnumAccess<'de> for MethodDeserializer<'a, D> {
+    type Error = D::Error;
+    type Variant = ParamsAccess<D>;
+
+    #[inline]
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(StrDeserializer::new(self.method))?;
+        Ok((variant, ParamsAccess(self.params)))
+    }
+}
+
+struct ParamsAccess<D>(D);
+
+impl<'de, D: Deserializer<'de>> VariantAccess<'de> for ParamsAccess<D> {
+    type Error = D::Error;
+
+    #[inline]
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        IgnoredAny::deserialize(self.0).map(|_| ())
+    }
+
+    #[inline]
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.0)
+    }
+
+    #[inline]
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_tuple(len, visitor)
+    }
+
+    #[inline]
+    fn struct_variant<V: Visitor<'de>>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_struct("", fields, visitor)
+    }
+}
+
+struct ParamsSeed<'a, M> {
+    method: &'a str,
+    _method: PhantomData<M>,
+}
+
+impl<'de, 'a, M: Deserialize<'de>> DeserializeSeed<'de> for ParamsSeed<'a, M> {
+    type Value = M;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, params: D) -> Result<Self::Value, D::Error> {
+        M::deserialize(MethodDeserializer {
+            method: self.method,
+            params,
+        })
+    }
+}
+
+struct DispatchMembers<M, T> {
+    variants: &'static [&'static str],
+    version: Option<Version>,
+    method: Option<T>,
+    //`Some(None)` indicates params of unknown method.
+    params: Option<Option<M>>,
+    //Params preceding `method`.
+    #[cfg(feature = "serde_json")]
+    buffer: Option<serde_json::Value>,
+    has_params: bool,
+    id: Option<Id>,
+    has_id: bool,
+}
+
+impl<'de, M: Deserialize<'de>, T: AsRef<str>> DispatchMembers<M, T> {
+    #[inline]
+    fn is_known(&self, method: &str) -> bool {
+        self.variants.contains(&method)
+    }
+
+    fn finish<E: de::Error>(self) -> Result<Dispatch<M, T>, E> {
+        let jsonrpc = match self.version {
+            Some(version) => version,
+            None => Version::V2,
+        };
+        let method = match self.method {
+            Some(method) => method,
+            None => return Err(E::missing_field("method")),
+        };
+        let params = match self.params {
+            Some(params) => Ok(params),
+            None if self.variants.contains(&method.as_ref()) => {
+                let params = ParamsSeed {
+                    method: method.as_ref(),
+                    _method: PhantomData::<M>,
+                };
+                #[cfg(feature = "serde_json")]
+                let params = match self.buffer {
+                    Some(buffer) => params.deserialize(buffer).map_err(|_| ()),
+                    None => params.deserialize(UnitDeserializer::<E>::new()).map_err(|_| ()),
+                };
+                #[cfg(not(feature = "serde_json"))]
+                let params = params.deserialize(UnitDeserializer::<E>::new()).map_err(|_| ());
+                params.map(Some)
+            },
+            None => Ok(None),
+        };
+
+        Ok(match params {
+            Ok(Some(params)) => Dispatch::Method(Request {
+                jsonrpc,
+                method,
+                params: Some(params),
+                id: self.id,
+            }),
+            Ok(None) => Dispatch::NotFound(Request {
+                jsonrpc,
+                method,
+                params: None,
+                id: self.id,
+            }),
+            Err(()) => Dispatch::InvalidParams(Request {
+                jsonrpc,
+                method,
+                params: None,
+                id: self.id,
+            }),
+        })
+    }
+}
+
+impl<'de, M: Deserialize<'de>, T: Deserialize<'de> + AsRef<str>> Members<'de> for DispatchMembers<M, T> {
+    type Key = Key;
+    const NAMES: &'static [&'static str] = <request::Members<(), T> as Members<'de>>::NAMES;
+
+    #[inline(always)]
+    fn key(text: &str) -> Option<Self::Key> {
+        <request::Members<(), T> as Members<'de>>::key(text)
+    }
+
+    fn visit_value<A: MapAccess<'de>>(&mut self, key: Self::Key, map: &mut A) -> Result<(), A::Error> {
+        match key {
+            Key::Version => Err(crate::utils::legacy_version()),
+            Key::JsonRpc if self.version.is_some() => Err(de::Error::duplicate_field("jsonrpc")),
+            Key::JsonRpc => {
+                self.version = Some(map.next_value()?);
+                Ok(())
+            },
+            Key::Method if self.method.is_some() => Err(de::Error::duplicate_field("method")),
+            Key::Method => {
+                self.method = Some(map.next_value()?);
+                Ok(())
+            },
+            Key::Params if self.has_params => Err(de::Error::duplicate_field("params")),
+            Key::Params => match self.method {
+                Some(ref method) if self.is_known(method.as_ref()) => {
+                    self.has_params = true;
+                    let params = map.next_value_seed(ParamsSeed {
+                        method: method.as_ref(),
+                        _method: PhantomData::<M>,
+                    })?;
+                    self.params = Some(Some(params));
+                    Ok(())
+                },
+                Some(_) => {
+                    self.has_params = true;
+                    map.next_value::<IgnoredAny>()?;
+                    self.params = Some(None);
+                    Ok(())
+                },
+                #[cfg(feature = "serde_json")]
+                None => {
+                    self.has_params = true;
+                    self.buffer = Some(map.next_value()?);
+                    Ok(())
+                },
+                #[cfg(not(feature = "serde_json"))]
+                None => Err(de::Error::custom("JSON-RPC Request `params` must follow `method`")),
+            },
+            Key::Id if self.has_id => Err(de::Error::duplicate_field("id")),
+            Key::Id => {
+                self.has_id = true;
+                self.id = map.next_value()?;
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<'de, M: Deserialize<'de>, T: Deserialize<'de> + AsRef<str>> Deserialize<'de> for Dispatch<M, T> {
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct MapVisit<M, T>(PhantomData<(M, T)>);
+
+        impl<'de, M: Deserialize<'de>, T: Deserialize<'de> + AsRef<str>> Visitor<'de> for MapVisit<M, T> {
+            type Value = Dispatch<M, T>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Object resembling JSON-RPC request type")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut members = DispatchMembers {
+                    variants: variants::<M>(),
+                    version: None,
+                    method: None,
+                    params: None,
+                    #[cfg(feature = "serde_json")]
+                    buffer: None,
+                    has_params: false,
+                    id: None,
+                    has_id: false,
+                };
+                crate::ext::visit_extensions::<_, _, ()>(&mut map, &mut members)?;
+                members.finish()
+            }
+        }
+
+        der.deserialize_map(MapVisit(PhantomData))
+    }
+}
@@ -1,9 +1,11 @@
 #[rustfmt::skip]
-
 use serde::{Serialize, Deserialize};
 use serde::de::{Deserializer};
 use serde::ser::{Serializer};
 
+use crate::id::Id;
+use crate::text::FromText;
+
 use core::fmt;
 use core::mem;
 
@@ -93,6 +95,50 @@ pub struct Error<T, M=StrBuf> {
     pub data: Option<T>,
 }
 
+///Error of building message out of text.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BuildError {
+    ///Method name cannot be converted into text type, e.g. due to insufficient capacity.
+    Method {
+        ///Length of method name.
+        len: usize,
+    },
+    ///Error message cannot be converted into text type, e.g. due to insufficient capacity.
+    Message {
+        ///Length of message.
+        len: usize,
+    },
+    ///Identifier cannot be converted into `Id`, e.g. due to insufficient capacity.
+    Id,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Method { len } => fmt.write_fmt(format_args!("Method name of {} bytes cannot be stored", len)),
+            BuildError::Message { len } => fmt.write_fmt(format_args!("Error message of {} bytes cannot be stored", len)),
+            BuildError::Id => fmt.write_str("Identifier cannot be stored"),
+        }
+    }
+}
+
+impl<'a, T, EM: FromText<'a>> Error<T, EM> {
+    #[inline]
+    ///Constructs error by converting message from string, failing if it cannot be converted.
+    pub fn try_with_text_message(code: ErrorCode, message: &'a str) -> Result<Self, BuildError> {
+        match EM::from_text(message) {
+            Some(message) => Ok(Self {
+                code,
+                message,
+                data: None,
+            }),
+            None => Err(BuildError::Message {
+                len: message.len(),
+            }),
+        }
+    }
+}
+
 impl<'a, T, EM: From<&'a str>> Error<T, EM> {
     #[inline]
     ///Constructs error by converting message from string.
@@ -105,7 +151,7 @@ impl<'a, T, EM: From<&'a str>> Error<T, EM> {
     }
 }
 
-impl<T> fmt::Display for Error<T> {
+impl<T, M> fmt::Display for Error<T, M> {
     #[inline(always)]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.write_str(self.code.message())
@@ -125,12 +171,9 @@ impl<const N: usize, T> Error<T, str_buf::StrBuf<N>> {
             storage.len()
         };
 
-        loop {
+        while idx < idx_limit {
             storage[idx] = mem::MaybeUninit::new(msg[idx]);
             idx += 1;
-            if idx == idx_limit {
-                break;
-            }
         }
 
         let message = unsafe {
@@ -145,16 +188,15 @@ impl<const N: usize, T> Error<T, str_buf::StrBuf<N>> {
     }
 
     ///Constructs error with custom message
+    ///
+    ///Panics if message doesn't fit, use `try_with_text_message` to handle it.
     pub const fn with_custom_msg(code: ErrorCode, message: &str) -> Self {
         let mut storage = [mem::MaybeUninit::uninit(); N];
         let msg = message.as_bytes();
         let mut idx = 0;
-        loop {
+        while idx < msg.len() {
             storage[idx] = mem::MaybeUninit::new(msg[idx]);
             idx += 1;
-            if idx == msg.len() {
-                break;
-            }
         }
 
         let message = unsafe {
@@ -184,3 +226,37 @@ impl<const N: usize, T> Error<T, str_buf::StrBuf<N>> {
         }
     }
 }
+
+///Failure of request from client's point of view.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ClientError<T, M=StrBuf> {
+    ///Server failed to process request, e.g. `ParseError` or `InvalidRequest`.
+    Protocol(Error<T, M>),
+    ///Server responded with error.
+    Rpc(Error<T, M>),
+    ///Response's `id` doesn't match request's `id`.
+    IdMismatch {
+        ///Request's `id`.
+        expected: Id,
+        ///Response's `id`.
+        actual: Option<Id>,
+    },
+}
+
+impl<T, M> From<Error<T, M>> for ClientError<T, M> {
+    #[inline(always)]
+    fn from(error: Error<T, M>) -> Self {
+        ClientError::Rpc(error)
+    }
+}
+
+impl<T, M: AsRef<str>> fmt::Display for ClientError<T, M> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Protocol(error) => fmt.write_fmt(format_args!("Protocol error: {}", error.message.as_ref())),
+            ClientError::Rpc(error) => fmt.write_str(error.message.as_ref()),
+            ClientError::IdMismatch { expected, actual: Some(actual) } => fmt.write_fmt(format_args!("Response id {} doesn't match request id {}", actual, expected)),
+            ClientError::IdMismatch { expected, actual: None } => fmt.write_fmt(format_args!("Response id is null, while request id is {}", expected)),
+        }
+    }
+}
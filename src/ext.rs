@@ -0,0 +1,488 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, DeserializeSeed, IgnoredAny, IntoDeserializer, MapAccess, Visitor};
+use serde::de::value::BorrowedStrDeserializer;
+use serde::ser::{self, Impossible, SerializeMap};
+
+use crate::request::{self, Request};
+use crate::response::{self, Response};
+
+use core::convert::Infallible;
+use core::fmt;
+use core::marker::PhantomData;
+
+///Collector of message's members, used to deserialize messages with extensions.
+pub(crate) trait Members<'de> {
+    type Key;
+    const NAMES: &'static [&'static str];
+
+    ///Returns key, if it belongs to the message.
+    fn key(text: &str) -> Option<Self::Key>;
+    ///Consumes value of the message's member.
+    fn visit_value<A: MapAccess<'de>>(&mut self, key: Self::Key, map: &mut A) -> Result<(), A::Error>;
+}
+
+///Wrapper to carry extension members alongside JSON-RPC message.
+///
+///Extension is (de)serialized from top level members that are not defined by specification,
+///which allows to carry arbitrary metadata, like trace context or authentication token.
+///
+///Type parameters:
+///
+///- `T` - Message, which is either `Request` or `Response`.
+///- `X` - Extension, which must be represented as map or struct. By default it is `()`, which rejects unknown members, same as message itself.
+///
+///Extension is carried by wrapper, rather than by type parameter of `Request` and `Response`,
+///because it would require new public field, breaking code that constructs messages with struct literal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extended<T, X=()> {
+    ///JSON-RPC message.
+    pub message: T,
+    ///Extension members.
+    pub extensions: X,
+}
+
+impl<T, X> Extended<T, X> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(message: T, extensions: X) -> Self {
+        Self {
+            message,
+            extensions,
+        }
+    }
+}
+
+impl<P: Serialize, T: Serialize, X: Serialize> Serialize for Extended<Request<P, T>, X> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut state = ser.serialize_map(None)?;
+        self.message.serialize_members(&mut state)?;
+        self.extensions.serialize(FlatMapSerializer(&mut state))?;
+        state.end()
+    }
+}
+
+impl<R: Serialize, E: Serialize, EM: Serialize, X: Serialize> Serialize for Extended<Response<R, E, EM>, X> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut state = ser.serialize_map(None)?;
+        self.message.serialize_members(&mut state)?;
+        self.extensions.serialize(FlatMapSerializer(&mut state))?;
+        state.end()
+    }
+}
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>, X: Deserialize<'de>> Deserialize<'de> for Extended<Request<P, T>, X> {
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct MapVisit<P, T, X>(PhantomData<(P, T, X)>);
+
+        impl<'de, P: Deserialize<'de>, T: Deserialize<'de>, X: Deserialize<'de>> Visitor<'de> for MapVisit<P, T, X> {
+            type Value = Extended<Request<P, T>, X>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Object resembling JSON-RPC request type")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut members = request::Members::new();
+                let extensions = visit_extensions(&mut map, &mut members)?;
+
+                Ok(Extended {
+                    message: members.finish()?,
+                    extensions,
+                })
+            }
+        }
+
+        der.deserialize_map(MapVisit(PhantomData))
+    }
+}
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>, X: Deserialize<'de>> Deserialize<'de> for Extended<Response<R, E, EM>, X> {
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct MapVisit<R, E, EM, X>(PhantomData<(R, E, EM, X)>);
+
+        impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>, X: Deserialize<'de>> Visitor<'de> for MapVisit<R, E, EM, X> {
+            type Value = Extended<Response<R, E, EM>, X>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Object resembling JSON-RPC response type")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut members = response::Members::new();
+                let extensions = visit_extensions(&mut map, &mut members)?;
+
+                Ok(Extended {
+                    message: members.finish()?,
+                    extensions,
+                })
+            }
+        }
+
+        der.deserialize_map(MapVisit(PhantomData))
+    }
+}
+
+///Deserializes `X` out of unknown members, while feeding known members into `members`
+pub(crate) fn visit_extensions<'de, A: MapAccess<'de>, M: Members<'de>, X: Deserialize<'de>>(map: &mut A, members: &mut M) -> Result<X, A::Error> {
+    let extensions = X::deserialize(ExtDeserializer {
+        map: &mut *map,
+        members: &mut *members,
+    })?;
+
+    //Extension might not consume whole map, so finish it, ignoring any unknown member.
+    loop {
+        let mut seed = Some(PhantomData::<IgnoredAny>);
+        match map.next_key_seed(KeySeed::<M, _>::new(&mut seed))? {
+            Some(Member::Known(key)) => members.visit_value(key, map)?,
+            Some(Member::Extension(_)) => {
+                map.next_value::<IgnoredAny>()?;
+            },
+            None => break Ok(extensions),
+        }
+    }
+}
+
+enum Member<K, V> {
+    Known(K),
+    Extension(V),
+}
+
+///Deserializes message's key or forwards it to `seed`, if it is unknown.
+///
+///`seed` is taken only when key is unknown.
+struct KeySeed<'a, M, S> {
+    seed: &'a mut Option<S>,
+    _members: PhantomData<M>,
+}
+
+impl<'a, M, S> KeySeed<'a, M, S> {
+    #[inline(always)]
+    fn new(seed: &'a mut Option<S>) -> Self {
+        Self {
+            seed,
+            _members: PhantomData,
+        }
+    }
+}
+
+impl<'de, 'a, M: Members<'de>, S: DeserializeSeed<'de>> DeserializeSeed<'de> for KeySeed<'a, M, S> {
+    type Value = Member<M::Key, S::Value>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, des: D) -> Result<Self::Value, D::Error> {
+        des.deserialize_identifier(self)
+    }
+}
+
+impl<'de, 'a, M: Members<'de>, S: DeserializeSeed<'de>> Visitor<'de> for KeySeed<'a, M, S> {
+    type Value = Member<M::Key, S::Value>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Key must be a string")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+        match M::key(text) {
+            Some(key) => Ok(Member::Known(key)),
+            None => match self.seed.take() {
+                Some(seed) => seed.deserialize(text.into_deserializer()).map(Member::Extension),
+                None => Err(de::Error::custom("Extension key is requested twice")),
+            },
+        }
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E: de::Error>(self, text: &'de str) -> Result<Self::Value, E> {
+        match M::key(text) {
+            Some(key) => Ok(Member::Known(key)),
+            None => match self.seed.take() {
+                Some(seed) => seed.deserialize(BorrowedStrDeserializer::new(text)).map(Member::Extension),
+                None => Err(de::Error::custom("Extension key is requested twice")),
+            },
+        }
+    }
+}
+
+///Seed, that fails on any unknown key.
+struct RejectKey<M>(PhantomData<M>);
+
+impl<'de, M: Members<'de>> DeserializeSeed<'de> for RejectKey<M> {
+    type Value = Infallible;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, des: D) -> Result<Self::Value, D::Error> {
+        des.deserialize_str(self)
+    }
+}
+
+impl<'de, M: Members<'de>> Visitor<'de> for RejectKey<M> {
+    type Value = Infallible;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Key must be a string")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+        Err(de::Error::unknown_field(text, M::NAMES))
+    }
+}
+
+///Deserializer over message's map, which exposes only unknown members.
+struct ExtDeserializer<'a, A, M> {
+    map: &'a mut A,
+    members: &'a mut M,
+}
+
+impl<'a, A, M> ExtDeserializer<'a, A, M> {
+    fn reject_unknown<'de>(self) -> Result<(), A::Error> where A: MapAccess<'de>, M: Members<'de> {
+        loop {
+            let mut seed = Some(RejectKey::<M>(PhantomData));
+            match self.map.next_key_seed(KeySeed::<M, _>::new(&mut seed))? {
+                Some(Member::Known(key)) => self.members.visit_value(key, &mut *self.map)?,
+                Some(Member::Extension(never)) => match never {},
+                None => break Ok(()),
+            }
+        }
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>, M: Members<'de>> Deserializer<'de> for ExtDeserializer<'a, A, M> {
+    type Error = A::Error;
+
+    #[inline]
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(self)
+    }
+
+    #[inline]
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    #[inline]
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.reject_unknown()?;
+        visitor.visit_unit()
+    }
+
+    #[inline]
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    #[inline]
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, 'a, A: MapAccess<'de>, M: Members<'de>> MapAccess<'de> for ExtDeserializer<'a, A, M> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        let mut seed = Some(seed);
+        loop {
+            match self.map.next_key_seed(KeySeed::<M, _>::new(&mut seed))? {
+                Some(Member::Known(key)) => self.members.visit_value(key, &mut *self.map)?,
+                Some(Member::Extension(key)) => break Ok(Some(key)),
+                None => break Ok(None),
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        self.map.next_value_seed(seed)
+    }
+}
+
+///Serializer, that writes map or struct as entries of message's map.
+struct FlatMapSerializer<'a, M>(&'a mut M);
+
+impl<'a, M: SerializeMap> FlatMapSerializer<'a, M> {
+    #[inline]
+    fn unsupported() -> M::Error {
+        ser::Error::custom("Extensions must be either map or struct")
+    }
+}
+
+impl<'a, M: SerializeMap> Serializer for FlatMapSerializer<'a, M> {
+    type Ok = ();
+    type Error = M::Error;
+    type SerializeSeq = Impossible<(), M::Error>;
+    type SerializeTuple = Impossible<(), M::Error>;
+    type SerializeTupleStruct = Impossible<(), M::Error>;
+    type SerializeTupleVariant = Impossible<(), M::Error>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), M::Error>;
+
+    fn serialize_bool(self, _: bool) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_char(self, _: char) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_str(self, _: &str) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, _: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _: &'static str, _: u32, _: &'static str, _: &T) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn serialize_tuple_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    #[inline]
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self)
+    }
+
+    #[inline]
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(self, _: &'static str, _: u32, _: &'static str, _: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(Self::unsupported())
+    }
+
+    fn collect_str<T: ?Sized + fmt::Display>(self, _: &T) -> Result<Self::Ok, Self::Error> {
+        Err(Self::unsupported())
+    }
+}
+
+impl<'a, M: SerializeMap> SerializeMap for FlatMapSerializer<'a, M> {
+    type Ok = ();
+    type Error = M::Error;
+
+    #[inline(always)]
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.0.serialize_key(key)
+    }
+
+    #[inline(always)]
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.0.serialize_value(value)
+    }
+
+    #[inline(always)]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl<'a, M: SerializeMap> ser::SerializeStruct for FlatMapSerializer<'a, M> {
+    type Ok = ();
+    type Error = M::Error;
+
+    #[inline(always)]
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.0.serialize_entry(key, value)
+    }
+
+    #[inline(always)]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
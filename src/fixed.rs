@@ -0,0 +1,32 @@
+//!Fixed capacity collections, backed by `heapless`.
+//!
+//!`heapless::String` can be used as text type of `Request::method` and `Error::message`.
+//!Use `Request::try_new` and `Error::try_with_text_message` to build them out of string, failing with `BuildError` when text doesn't fit.
+
+use crate::request::{self, Request};
+use crate::response::Response;
+use crate::error;
+
+///Batch of messages with capacity `N`.
+///
+///Deserialization fails if batch contains more than `N` messages.
+///Note that specification treats empty batch as invalid, which is up to user to check.
+pub type Batch<T, const N: usize> = heapless::Vec<T, N>;
+
+///Batch of requests with capacity `N`.
+///
+///```rust
+///use json_rpc_types::{Request, RequestBatch};
+///
+///let mut batch = RequestBatch::<[u32; 2], 2>::new();
+///batch.push(Request::new(json_rpc_types::str_buf::StrBuf::from_str("sum")).set_params([1, 2]).set_id(1)).unwrap();
+///batch.push(Request::new(json_rpc_types::str_buf::StrBuf::from_str("notify"))).unwrap();
+///assert!(batch.push(Request::new(json_rpc_types::str_buf::StrBuf::from_str("overflow"))).is_err());
+///```
+pub type RequestBatch<P, const N: usize, T = request::StrBuf> = Batch<Request<P, T>, N>;
+
+///Batch of responses with capacity `N`.
+pub type ResponseBatch<R, E, const N: usize, EM = error::StrBuf> = Batch<Response<R, E, EM>, N>;
+
+///Positional params of up to `N` elements, serialized as array.
+pub type ArrayParams<T, const N: usize> = heapless::Vec<T, N>;
@@ -0,0 +1,51 @@
+use defmt::{Format, Formatter};
+
+use crate::version::Version;
+use crate::id::Id;
+use crate::error::{Error, ErrorCode};
+use crate::request::Request;
+use crate::response::Response;
+
+impl Format for Version {
+    #[inline]
+    fn format(&self, fmt: Formatter<'_>) {
+        defmt::write!(fmt, "{=str}", self.as_str())
+    }
+}
+
+impl Format for Id {
+    fn format(&self, fmt: Formatter<'_>) {
+        match self {
+            Id::Num(id) => defmt::write!(fmt, "{=u64}", id),
+            Id::Str(id) => defmt::write!(fmt, "\"{=str}\"", id.as_str()),
+        }
+    }
+}
+
+impl Format for ErrorCode {
+    #[inline]
+    fn format(&self, fmt: Formatter<'_>) {
+        defmt::write!(fmt, "{=i64}({=str})", self.code(), self.message())
+    }
+}
+
+impl<T: Format, M: AsRef<str>> Format for Error<T, M> {
+    fn format(&self, fmt: Formatter<'_>) {
+        defmt::write!(fmt, "Error {{ code: {}, message: {=str}, data: {} }}", self.code, self.message.as_ref(), self.data)
+    }
+}
+
+impl<P: Format, T: AsRef<str>> Format for Request<P, T> {
+    fn format(&self, fmt: Formatter<'_>) {
+        defmt::write!(fmt, "Request {{ jsonrpc: {}, method: {=str}, params: {}, id: {} }}", self.jsonrpc, self.method.as_ref(), self.params, self.id)
+    }
+}
+
+impl<R: Format, E: Format, EM: AsRef<str>> Format for Response<R, E, EM> {
+    fn format(&self, fmt: Formatter<'_>) {
+        match &self.payload {
+            Ok(result) => defmt::write!(fmt, "Response {{ jsonrpc: {}, result: {}, id: {} }}", self.jsonrpc, result, self.id),
+            Err(error) => defmt::write!(fmt, "Response {{ jsonrpc: {}, error: {}, id: {} }}", self.jsonrpc, error, self.id),
+        }
+    }
+}
@@ -0,0 +1,459 @@
+use core::fmt;
+
+///Encoding of frames.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Encoding {
+    ///Consistent Overhead Byte Stuffing, frame is terminated by `0x00`.
+    Cobs,
+    ///Serial Line Internet Protocol (RFC 1055), frame is terminated by `0xC0`.
+    Slip,
+}
+
+///Checksum, appended to payload before encoding.
+///
+///Checksum is stored in big endian order.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Checksum {
+    ///No checksum.
+    None,
+    ///CRC-16/CCITT-FALSE.
+    Crc16,
+    ///CRC-32/ISO-HDLC, as used by Ethernet and zlib.
+    Crc32,
+}
+
+impl Checksum {
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    ///Returns number of bytes taken by checksum.
+    pub const fn len(&self) -> usize {
+        match self {
+            Checksum::None => 0,
+            Checksum::Crc16 => 2,
+            Checksum::Crc32 => 4,
+        }
+    }
+
+    ///Computes CRC-16/CCITT-FALSE of `bytes`.
+    pub const fn crc16(bytes: &[u8]) -> u16 {
+        let mut crc = 0xFFFFu16;
+        let mut idx = 0;
+        while idx < bytes.len() {
+            crc ^= (bytes[idx] as u16) << 8;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = match crc & 0x8000 {
+                    0 => crc << 1,
+                    _ => (crc << 1) ^ 0x1021,
+                };
+                bit += 1;
+            }
+            idx += 1;
+        }
+        crc
+    }
+
+    ///Computes CRC-32/ISO-HDLC of `bytes`.
+    pub const fn crc32(bytes: &[u8]) -> u32 {
+        let mut crc = 0xFFFF_FFFFu32;
+        let mut idx = 0;
+        while idx < bytes.len() {
+            crc ^= bytes[idx] as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = match crc & 1 {
+                    0 => crc >> 1,
+                    _ => (crc >> 1) ^ 0xEDB8_8320,
+                };
+                bit += 1;
+            }
+            idx += 1;
+        }
+        !crc
+    }
+
+    ///Computes checksum of `bytes`, returning it with its length.
+    fn compute(&self, bytes: &[u8]) -> ([u8; 4], usize) {
+        let mut result = [0; 4];
+        match self {
+            Checksum::None => (),
+            Checksum::Crc16 => result[..2].copy_from_slice(&Self::crc16(bytes).to_be_bytes()),
+            Checksum::Crc32 => result.copy_from_slice(&Self::crc32(bytes).to_be_bytes()),
+        }
+        (result, self.len())
+    }
+}
+
+///Error of framing.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FrameError {
+    ///Buffer is too small to fit frame.
+    Overflow,
+    ///Frame is not valid according to encoding.
+    Malformed,
+    ///Frame's checksum doesn't match its payload.
+    Checksum,
+    ///Message cannot be serialized.
+    Serialize,
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::Overflow => fmt.write_str("Buffer is too small to fit frame"),
+            FrameError::Malformed => fmt.write_str("Frame has invalid encoding"),
+            FrameError::Checksum => fmt.write_str("Frame checksum mismatch"),
+            FrameError::Serialize => fmt.write_str("Message cannot be serialized"),
+        }
+    }
+}
+
+const COBS_END: u8 = 0x00;
+const COBS_MAX_CODE: u8 = 0xFF;
+
+const SLIP_END: u8 = 0xC0;
+const SLIP_ESC: u8 = 0xDB;
+const SLIP_ESC_END: u8 = 0xDC;
+const SLIP_ESC_ESC: u8 = 0xDD;
+
+///Framing of messages over byte stream, such as serial link.
+///
+///```rust
+///use json_rpc_types::{Framing, Encoding, Checksum, FrameDecoder};
+///
+///const FRAMING: Framing = Framing::new(Encoding::Cobs, Checksum::Crc16);
+///
+///let mut frame = [0u8; 64];
+///let len = FRAMING.encode(br#"{"jsonrpc":"2.0","method":"ping"}"#, &mut frame).unwrap();
+///
+///let mut decoder = FrameDecoder::<64>::new(FRAMING);
+///let (last, head) = frame[..len].split_last().unwrap();
+///for byte in head {
+///    assert!(decoder.push(*byte).is_none());
+///}
+///assert_eq!(decoder.push(*last), Some(Ok(&br#"{"jsonrpc":"2.0","method":"ping"}"#[..])));
+///```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Framing {
+    ///Encoding of frame.
+    pub encoding: Encoding,
+    ///Checksum appended to payload.
+    pub checksum: Checksum,
+}
+
+impl Framing {
+    #[inline]
+    ///Creates new instance.
+    pub const fn new(encoding: Encoding, checksum: Checksum) -> Self {
+        Self {
+            encoding,
+            checksum,
+        }
+    }
+
+    ///Returns maximum length of frame with payload of `len` bytes, including terminator.
+    pub const fn max_encoded_len(&self, len: usize) -> usize {
+        let len = len + self.checksum.len();
+        match self.encoding {
+            //Code byte per each 254 bytes and terminator.
+            Encoding::Cobs => len + len / 254 + 2,
+            //Every byte might be escaped.
+            Encoding::Slip => 2 * len + 1,
+        }
+    }
+
+    ///Encodes `payload` into `out`, returning length of frame, including terminator.
+    ///
+    ///Requires `out` to fit `max_encoded_len` of payload.
+    pub fn encode(&self, payload: &[u8], out: &mut [u8]) -> Result<usize, FrameError> {
+        let max_len = self.max_encoded_len(payload.len());
+        if out.len() < max_len {
+            return Err(FrameError::Overflow);
+        }
+
+        let start = max_len - payload.len() - self.checksum.len();
+        out[start..start + payload.len()].copy_from_slice(payload);
+        Ok(self.encode_in_place(out, start, payload.len()))
+    }
+
+    ///Encodes payload stored in `buffer` at `start`, writing frame from beginning of `buffer`.
+    ///
+    ///Payload must be stored at the end of `max_encoded_len` bytes of `buffer`, leaving space for checksum,
+    ///so that encoding never overwrites payload's bytes which are not read yet.
+    fn encode_in_place(&self, buffer: &mut [u8], start: usize, len: usize) -> usize {
+        let (checksum, checksum_len) = self.checksum.compute(&buffer[start..start + len]);
+        let checksum = &checksum[..checksum_len];
+
+        match self.encoding {
+            Encoding::Cobs => {
+                let mut code_idx = 0;
+                let mut write = 1;
+                let mut code = 1u8;
+                //Block of maximum length at the end of input doesn't need trailing empty block.
+                let mut is_closed = false;
+
+                for idx in 0..len + checksum_len {
+                    let byte = match idx < len {
+                        true => buffer[start + idx],
+                        false => checksum[idx - len],
+                    };
+
+                    if byte == COBS_END {
+                        buffer[code_idx] = code;
+                        code_idx = write;
+                        write += 1;
+                        code = 1;
+                    } else {
+                        buffer[write] = byte;
+                        write += 1;
+                        code += 1;
+                        if code == COBS_MAX_CODE {
+                            buffer[code_idx] = code;
+                            code = 1;
+                            if idx + 1 < len + checksum_len {
+                                code_idx = write;
+                                write += 1;
+                            } else {
+                                is_closed = true;
+                            }
+                        }
+                    }
+                }
+
+                if !is_closed {
+                    buffer[code_idx] = code;
+                }
+                buffer[write] = COBS_END;
+                write + 1
+            },
+            Encoding::Slip => {
+                let mut write = 0;
+
+                for idx in 0..len + checksum_len {
+                    let byte = match idx < len {
+                        true => buffer[start + idx],
+                        false => checksum[idx - len],
+                    };
+
+                    match byte {
+                        SLIP_END => {
+                            buffer[write] = SLIP_ESC;
+                            buffer[write + 1] = SLIP_ESC_END;
+                            write += 2;
+                        },
+                        SLIP_ESC => {
+                            buffer[write] = SLIP_ESC;
+                            buffer[write + 1] = SLIP_ESC_ESC;
+                            write += 2;
+                        },
+                        byte => {
+                            buffer[write] = byte;
+                            write += 1;
+                        },
+                    }
+                }
+
+                buffer[write] = SLIP_END;
+                write + 1
+            },
+        }
+    }
+}
+
+///Streaming decoder of frames, accepting input byte by byte.
+///
+///Decoded frame, including checksum, must fit buffer of `N` bytes.
+///Empty frames are ignored, so terminator can be sent before frame to flush line noise.
+#[derive(Debug, Clone)]
+pub struct FrameDecoder<const N: usize> {
+    framing: Framing,
+    buffer: [u8; N],
+    len: usize,
+    //Error encountered in current frame, reported on its end.
+    error: Option<FrameError>,
+    //Whether previous byte completed frame, so buffer is to be reset.
+    complete: bool,
+    //COBS: whether frame has started.
+    //SLIP: whether escape byte is pending.
+    flag: bool,
+    //COBS: code of current block.
+    code: u8,
+    //COBS: number of bytes remaining in current block.
+    left: u8,
+}
+
+impl<const N: usize> FrameDecoder<N> {
+    #[inline]
+    ///Creates new instance.
+    pub const fn new(framing: Framing) -> Self {
+        Self {
+            framing,
+            buffer: [0; N],
+            len: 0,
+            error: None,
+            complete: false,
+            flag: false,
+            code: 0,
+            left: 0,
+        }
+    }
+
+    #[inline]
+    ///Discards partially received frame.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.error = None;
+        self.complete = false;
+        self.flag = false;
+        self.code = 0;
+        self.left = 0;
+    }
+
+    #[inline]
+    fn store(&mut self, byte: u8) {
+        if self.error.is_some() {
+            return;
+        }
+
+        match self.buffer.get_mut(self.len) {
+            Some(slot) => {
+                *slot = byte;
+                self.len += 1;
+            },
+            None => self.error = Some(FrameError::Overflow),
+        }
+    }
+
+    ///Feeds `byte` into decoder, returning payload once frame is complete.
+    ///
+    ///Once error is encountered, the rest of frame is discarded and error is returned on its end.
+    pub fn push(&mut self, byte: u8) -> Option<Result<&[u8], FrameError>> {
+        if self.complete {
+            self.reset();
+        }
+
+        match self.framing.encoding {
+            Encoding::Cobs => {
+                if byte == COBS_END {
+                    if !self.flag {
+                        return None;
+                    } else if self.left != 0 {
+                        self.error = Some(FrameError::Malformed);
+                    }
+                    return self.finish();
+                }
+
+                if self.left == 0 {
+                    if self.flag && self.code != COBS_MAX_CODE {
+                        self.store(COBS_END);
+                    }
+                    self.flag = true;
+                    self.code = byte;
+                    self.left = byte - 1;
+                } else {
+                    self.store(byte);
+                    self.left -= 1;
+                }
+            },
+            Encoding::Slip => {
+                if self.flag {
+                    self.flag = false;
+                    match byte {
+                        SLIP_ESC_END => self.store(SLIP_END),
+                        SLIP_ESC_ESC => self.store(SLIP_ESC),
+                        //Terminator always ends frame, even if it is escaped.
+                        SLIP_END => {
+                            self.error = Some(FrameError::Malformed);
+                            return self.finish();
+                        },
+                        _ => self.error = Some(FrameError::Malformed),
+                    }
+                    return None;
+                }
+
+                match byte {
+                    SLIP_END => {
+                        if self.len == 0 && self.error.is_none() {
+                            return None;
+                        }
+                        return self.finish();
+                    },
+                    SLIP_ESC => self.flag = true,
+                    byte => self.store(byte),
+                }
+            },
+        }
+
+        None
+    }
+
+    fn finish(&mut self) -> Option<Result<&[u8], FrameError>> {
+        self.complete = true;
+        if let Some(error) = self.error {
+            return Some(Err(error));
+        }
+
+        let checksum_len = self.framing.checksum.len();
+        if self.len < checksum_len {
+            return Some(Err(FrameError::Checksum));
+        }
+
+        let (payload, checksum) = self.buffer[..self.len].split_at(self.len - checksum_len);
+        let (expected, _) = self.framing.checksum.compute(payload);
+        match checksum == &expected[..checksum_len] {
+            true => Some(Ok(payload)),
+            false => Some(Err(FrameError::Checksum)),
+        }
+    }
+}
+
+#[cfg(feature = "serde-json-core")]
+mod json {
+    use serde::Serialize;
+
+    use super::{Framing, FrameError};
+    use crate::request::Request;
+    use crate::response::Response;
+
+    ///Serializes `message` as JSON and encodes it in place.
+    fn to_frame<T: Serialize>(message: &T, framing: &Framing, out: &mut [u8]) -> Result<usize, FrameError> {
+        let len = match serde_json_core::to_slice(message, out) {
+            Ok(len) => len,
+            Err(serde_json_core::ser::Error::BufferFull) => return Err(FrameError::Overflow),
+            Err(_) => return Err(FrameError::Serialize),
+        };
+
+        let max_len = framing.max_encoded_len(len);
+        if out.len() < max_len {
+            return Err(FrameError::Overflow);
+        }
+
+        let start = max_len - len - framing.checksum.len();
+        out.copy_within(..len, start);
+        Ok(framing.encode_in_place(out, start, len))
+    }
+
+    impl<P: Serialize, T: Serialize> Request<P, T> {
+        #[inline]
+        ///Serializes JSON into `out` as frame, returning length of frame.
+        ///
+        ///Frame is encoded in place, so `out` must fit `max_encoded_len` of JSON.
+        ///
+        ///Requires `serde-json-core` feature in addition to `framing`.
+        pub fn to_frame(&self, framing: &Framing, out: &mut [u8]) -> Result<usize, FrameError> {
+            to_frame(self, framing, out)
+        }
+    }
+
+    impl<R: Serialize, E: Serialize, EM: Serialize> Response<R, E, EM> {
+        #[inline]
+        ///Serializes JSON into `out` as frame, returning length of frame.
+        ///
+        ///Frame is encoded in place, so `out` must fit `max_encoded_len` of JSON.
+        ///
+        ///Requires `serde-json-core` feature in addition to `framing`.
+        pub fn to_frame(&self, framing: &Framing, out: &mut [u8]) -> Result<usize, FrameError> {
+            to_frame(self, framing, out)
+        }
+    }
+}
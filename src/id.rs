@@ -1,12 +1,15 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use serde::de::{Error, Visitor};
+use serde::de::{Error, IgnoredAny, MapAccess, SeqAccess, Visitor};
 
-type StrBuf = str_buf::StrBuf<36>;
+pub(crate) type StrBuf = str_buf::StrBuf<36>;
 
 use core::fmt;
 
 ///Request identfier
-#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+///
+///Ordering is based on content: numbers go before strings, numbers are ordered by value and strings
+///lexicographically.
+#[derive(Debug, PartialEq, Clone, Hash, Eq, PartialOrd, Ord)]
 pub enum Id {
     /// Numeric id
     Num(u64),
@@ -14,6 +17,282 @@ pub enum Id {
     Str(StrBuf),
 }
 
+const TAG_NUM: u8 = 0;
+const TAG_STR: u8 = 1;
+
+impl Id {
+    ///Maximum size of binary encoding.
+    pub const MAX_ENCODED_LEN: usize = 2 + StrBuf::capacity();
+
+    ///Returns size of binary encoding.
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            Id::Num(mut id) => {
+                let mut len = 2;
+                while id >= 0x80 {
+                    id >>= 7;
+                    len += 1;
+                }
+                len
+            },
+            Id::Str(id) => 2 + id.len(),
+        }
+    }
+
+    ///Writes compact binary encoding into `buffer`, returning number of written bytes.
+    ///
+    ///Encoding consists of tag byte, followed by either LEB128 varint for number or length
+    ///byte and UTF-8 bytes for string.
+    ///
+    ///Returns `None` if `buffer` is too small, `MAX_ENCODED_LEN` is always enough.
+    pub fn encode(&self, buffer: &mut [u8]) -> Option<usize> {
+        let len = self.encoded_len();
+        let buffer = buffer.get_mut(..len)?;
+
+        match self {
+            Id::Num(mut id) => {
+                buffer[0] = TAG_NUM;
+                for byte in buffer[1..].iter_mut() {
+                    *byte = (id as u8 & 0x7f) | 0x80;
+                    id >>= 7;
+                }
+                buffer[len - 1] &= 0x7f;
+            },
+            Id::Str(id) => {
+                buffer[0] = TAG_STR;
+                buffer[1] = id.len() as u8;
+                buffer[2..].copy_from_slice(id.as_bytes());
+            },
+        }
+
+        Some(len)
+    }
+
+    ///Reads binary encoding, written by `encode`, returning identifier and number of consumed bytes.
+    ///
+    ///Returns `None` if `buffer` doesn't start with valid encoding, including non-canonical varint with redundant groups.
+    pub fn decode(buffer: &[u8]) -> Option<(Self, usize)> {
+        match *buffer.first()? {
+            TAG_NUM => {
+                let mut id = 0u64;
+                for (idx, byte) in buffer[1..].iter().enumerate() {
+                    let shift = idx * 7;
+                    let value = (*byte & 0x7f) as u64;
+                    //Overlong or overflowing varint is not produced by `encode`
+                    if shift >= 64 || (value << shift) >> shift != value {
+                        return None;
+                    }
+                    id |= value << shift;
+
+                    if *byte & 0x80 == 0 {
+                        //Trailing zero group is redundant, hence its encoding is not canonical
+                        if *byte == 0 && idx > 0 {
+                            return None;
+                        }
+                        return Some((Id::Num(id), idx + 2));
+                    }
+                }
+                None
+            },
+            TAG_STR => {
+                let len = *buffer.get(1)? as usize;
+                let text = buffer.get(2..2 + len)?;
+                let text = core::str::from_utf8(text).ok()?;
+                let id = StrBuf::from_str_checked(text).ok()?;
+                Some((Id::Str(id), 2 + len))
+            },
+            _ => None,
+        }
+    }
+}
+
+impl From<u64> for Id {
+    #[inline(always)]
+    fn from(id: u64) -> Self {
+        Id::Num(id)
+    }
+}
+
+impl From<StrBuf> for Id {
+    #[inline(always)]
+    fn from(id: StrBuf) -> Self {
+        Id::Str(id)
+    }
+}
+
+///Error of converting text into `Id`, either as is or from its JSON form.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseIdError {
+    ///Text is neither unsigned integer nor valid quoted string.
+    Invalid,
+    ///String doesn't fit `Id`.
+    Overflow,
+}
+
+impl fmt::Display for ParseIdError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseIdError::Invalid => fmt.write_str("Identifier must be either unsigned integer or quoted string"),
+            ParseIdError::Overflow => fmt.write_str("Identifier string exceeds 36 bytes"),
+        }
+    }
+}
+
+#[inline]
+fn push_char(id: &mut StrBuf, ch: char) -> Result<(), ParseIdError> {
+    let mut buffer = [0u8; 4];
+    let ch = ch.encode_utf8(&mut buffer);
+    if id.remaining() < ch.len() {
+        return Err(ParseIdError::Overflow);
+    }
+    id.push_str(ch);
+    Ok(())
+}
+
+#[inline]
+fn parse_hex(chars: &mut core::str::Chars<'_>) -> Result<u32, ParseIdError> {
+    let mut code = 0;
+    for _ in 0..4 {
+        match chars.next().and_then(|ch| ch.to_digit(16)) {
+            Some(digit) => code = code * 16 + digit,
+            None => return Err(ParseIdError::Invalid),
+        }
+    }
+    Ok(code)
+}
+
+fn parse_str(text: &str) -> Result<StrBuf, ParseIdError> {
+    let mut id = StrBuf::new();
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        let ch = match ch {
+            '\\' => match chars.next() {
+                Some('"') => '"',
+                Some('\\') => '\\',
+                Some('/') => '/',
+                Some('b') => '\u{8}',
+                Some('f') => '\u{c}',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('t') => '\t',
+                Some('u') => {
+                    let code = match parse_hex(&mut chars)? {
+                        high @ 0xD800..=0xDBFF => match (chars.next(), chars.next()) {
+                            (Some('\\'), Some('u')) => match parse_hex(&mut chars)? {
+                                low @ 0xDC00..=0xDFFF => 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00),
+                                _ => return Err(ParseIdError::Invalid),
+                            },
+                            _ => return Err(ParseIdError::Invalid),
+                        },
+                        code => code,
+                    };
+                    match core::char::from_u32(code) {
+                        Some(ch) => ch,
+                        None => return Err(ParseIdError::Invalid),
+                    }
+                },
+                _ => return Err(ParseIdError::Invalid),
+            },
+            '"' => return Err(ParseIdError::Invalid),
+            ch if (ch as u32) < 0x20 => return Err(ParseIdError::Invalid),
+            ch => ch,
+        };
+        push_char(&mut id, ch)?;
+    }
+    Ok(id)
+}
+
+impl<'a> core::convert::TryFrom<&'a str> for Id {
+    type Error = ParseIdError;
+
+    #[inline]
+    ///Wraps text as `Str`, failing with `Overflow` if it doesn't fit.
+    ///
+    ///Unlike `FromStr`, text is taken as it is, e.g. `"42"` becomes `Str`, use `parse` for JSON form.
+    fn try_from(id: &'a str) -> Result<Self, Self::Error> {
+        match StrBuf::from_str_checked(id) {
+            Ok(id) => Ok(Id::Str(id)),
+            Err(_) => Err(ParseIdError::Overflow),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::convert::TryFrom<alloc::string::String> for Id {
+    type Error = ParseIdError;
+
+    #[inline(always)]
+    ///Wraps text as `Str`, the same as `TryFrom<&str>`.
+    fn try_from(id: alloc::string::String) -> Result<Self, Self::Error> {
+        Self::try_from(id.as_str())
+    }
+}
+
+impl core::str::FromStr for Id {
+    type Err = ParseIdError;
+
+    ///Parses JSON form of identifier, as written by `Display`.
+    ///
+    ///Unsigned integer is parsed as `Num`, and quoted string as `Str`, while anything else is invalid.
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        if id.len() >= 2 && id.starts_with('"') && id.ends_with('"') {
+            parse_str(&id[1..id.len() - 1]).map(Id::Str)
+        } else if !id.is_empty() && id.bytes().all(|byte| byte.is_ascii_digit()) {
+            id.parse().map(Id::Num).map_err(|_| ParseIdError::Invalid)
+        } else {
+            Err(ParseIdError::Invalid)
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl From<uuid::Uuid> for Id {
+    #[inline]
+    ///Converts into hyphenated lowercase string.
+    fn from(id: uuid::Uuid) -> Self {
+        let mut buffer = [0u8; uuid::fmt::Hyphenated::LENGTH];
+        Id::Str(StrBuf::from_str(id.hyphenated().encode_lower(&mut buffer)))
+    }
+}
+
+#[cfg(feature = "ulid")]
+impl From<ulid::Ulid> for Id {
+    #[inline]
+    ///Converts into canonical string.
+    fn from(id: ulid::Ulid) -> Self {
+        let mut buffer = [0u8; ulid::ULID_LEN];
+        Id::Str(StrBuf::from_str(id.array_to_str(&mut buffer)))
+    }
+}
+
+impl fmt::Display for Id {
+    ///Writes identifier as JSON value, i.e. string is quoted and escaped.
+    ///
+    ///Output can be parsed back via `FromStr`.
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        match self {
+            Id::Num(id) => fmt::Display::fmt(id, fmt),
+            Id::Str(id) => {
+                fmt.write_char('"')?;
+                for ch in id.as_str().chars() {
+                    match ch {
+                        '"' => fmt.write_str("\\\"")?,
+                        '\\' => fmt.write_str("\\\\")?,
+                        '\n' => fmt.write_str("\\n")?,
+                        '\r' => fmt.write_str("\\r")?,
+                        '\t' => fmt.write_str("\\t")?,
+                        ch if (ch as u32) < 0x20 => fmt.write_fmt(format_args!("\\u{:04x}", ch as u32))?,
+                        ch => fmt.write_char(ch)?,
+                    }
+                }
+                fmt.write_char('"')
+            }
+        }
+    }
+}
+
 impl Serialize for Id {
     #[inline]
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
@@ -87,3 +366,166 @@ impl<'a> Visitor<'a> for IdVisitor {
         }
     }
 }
+
+///Identifier of message, recovered without deserializing any other member.
+///
+///Intended to be used on malformed request, so that error response can carry request's `id`,
+///instead of `null`.
+///
+///- Unknown members are ignored without validation;
+///- `id`, which is not valid `Id`, is ignored;
+///- If message contains multiple `id` members, the last valid one is used.
+///
+///Input still must be valid map, otherwise deserialization fails.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct IdOnly(pub Option<Id>);
+
+impl IdOnly {
+    #[inline(always)]
+    ///Returns recovered identifier, if any.
+    pub fn into_inner(self) -> Option<Id> {
+        self.0
+    }
+}
+
+impl<'a> Deserialize<'a> for IdOnly {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        des.deserialize_map(IdOnlyVisitor)
+    }
+}
+
+struct IdOnlyVisitor;
+
+impl<'a> Visitor<'a> for IdOnlyVisitor {
+    type Value = IdOnly;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Object resembling JSON-RPC message")
+    }
+
+    fn visit_map<A: MapAccess<'a>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut id = None;
+
+        while let Some(key) = map.next_key::<IdKey>()? {
+            match key {
+                IdKey::Id => if let Some(value) = map.next_value::<MaybeId>()?.0 {
+                    id = Some(value);
+                },
+                IdKey::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                },
+            }
+        }
+
+        Ok(IdOnly(id))
+    }
+}
+
+enum IdKey {
+    Id,
+    Other,
+}
+
+impl<'a> Deserialize<'a> for IdKey {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'a> Visitor<'a> for KeyVisitor {
+            type Value = IdKey;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Key must be a string")
+            }
+
+            #[inline]
+            fn visit_str<E: Error>(self, text: &str) -> Result<Self::Value, E> {
+                match text {
+                    "id" => Ok(IdKey::Id),
+                    _ => Ok(IdKey::Other),
+                }
+            }
+        }
+
+        des.deserialize_identifier(KeyVisitor)
+    }
+}
+
+///Accepts any value, producing `Id` only if value is valid identifier.
+struct MaybeId(Option<Id>);
+
+impl<'a> Deserialize<'a> for MaybeId {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        des.deserialize_any(MaybeIdVisitor)
+    }
+}
+
+struct MaybeIdVisitor;
+
+impl<'a> Visitor<'a> for MaybeIdVisitor {
+    type Value = MaybeId;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Any value")
+    }
+
+    #[inline]
+    fn visit_bool<E: Error>(self, _: bool) -> Result<Self::Value, E> {
+        Ok(MaybeId(None))
+    }
+
+    #[inline]
+    fn visit_u64<E: Error>(self, id: u64) -> Result<Self::Value, E> {
+        Ok(MaybeId(IdVisitor.visit_u64::<E>(id).ok()))
+    }
+
+    #[inline]
+    fn visit_i64<E: Error>(self, id: i64) -> Result<Self::Value, E> {
+        Ok(MaybeId(IdVisitor.visit_i64::<E>(id).ok()))
+    }
+
+    #[inline]
+    fn visit_f64<E: Error>(self, _: f64) -> Result<Self::Value, E> {
+        Ok(MaybeId(None))
+    }
+
+    #[inline]
+    fn visit_str<E: Error>(self, id: &str) -> Result<Self::Value, E> {
+        Ok(MaybeId(IdVisitor.visit_str::<E>(id).ok()))
+    }
+
+    #[inline]
+    fn visit_bytes<E: Error>(self, _: &[u8]) -> Result<Self::Value, E> {
+        Ok(MaybeId(None))
+    }
+
+    #[inline]
+    fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(MaybeId(None))
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'a>>(self, des: D) -> Result<Self::Value, D::Error> {
+        MaybeId::deserialize(des)
+    }
+
+    #[inline]
+    fn visit_unit<E: Error>(self) -> Result<Self::Value, E> {
+        Ok(MaybeId(None))
+    }
+
+    #[inline]
+    fn visit_seq<A: SeqAccess<'a>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        IgnoredAny.visit_seq(seq).map(|_| MaybeId(None))
+    }
+
+    #[inline]
+    fn visit_map<A: MapAccess<'a>>(self, map: A) -> Result<Self::Value, A::Error> {
+        IgnoredAny.visit_map(map).map(|_| MaybeId(None))
+    }
+}
@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+
+use crate::version::Version;
+use crate::id::Id;
+use crate::error::{Error, ErrorCode};
+use crate::request::Request;
+use crate::response::Response;
+
+///Type with known upper bound of serialized JSON length.
+///
+///Intended to size static buffers at compile time:
+///
+///```rust
+///use json_rpc_types::{MaxSize, Request};
+///
+///type Call = Request<[u32; 2]>;
+///
+///let mut buffer = [0u8; <Call as MaxSize>::MAX_SIZE];
+///let call = Call::new(json_rpc_types::str_buf::StrBuf::from_str("sum")).set_params([1, 2]).set_id(1);
+///let len = call.to_slice(&mut buffer).unwrap();
+///assert_eq!(&buffer[..len], br#"{"jsonrpc":"2.0","method":"sum","params":[1,2],"id":1}"#);
+///```
+pub trait MaxSize {
+    ///Maximum number of bytes in serialized form, accounting for string escaping.
+    const MAX_SIZE: usize;
+}
+
+#[inline(always)]
+const fn max(left: usize, right: usize) -> usize {
+    if left > right {
+        left
+    } else {
+        right
+    }
+}
+
+///Length of `null`
+const NULL: usize = 4;
+
+macro_rules! impl_max_size {
+    ($($typ:ty => $size:expr,)+) => {
+        $(
+            impl MaxSize for $typ {
+                const MAX_SIZE: usize = $size;
+            }
+        )+
+    };
+}
+
+impl_max_size!(
+    () => NULL,
+    bool => 5,
+    u8 => 3,
+    u16 => 5,
+    u32 => 10,
+    u64 => 20,
+    usize => 20,
+    i8 => 4,
+    i16 => 6,
+    i32 => 11,
+    i64 => 20,
+    isize => 20,
+    Version => 5,
+    ErrorCode => 20,
+);
+
+impl<T: MaxSize> MaxSize for Option<T> {
+    const MAX_SIZE: usize = max(T::MAX_SIZE, NULL);
+}
+
+impl<T: MaxSize, const N: usize> MaxSize for [T; N] {
+    //Brackets and commas between elements.
+    const MAX_SIZE: usize = 2 + N * T::MAX_SIZE + if N > 0 { N - 1 } else { 0 };
+}
+
+macro_rules! impl_max_size_tuple {
+    ($($len:literal => ($($typ:ident),+),)+) => {
+        $(
+            impl<$($typ: MaxSize),+> MaxSize for ($($typ,)+) {
+                const MAX_SIZE: usize = 2 + ($len - 1) $(+ $typ::MAX_SIZE)+;
+            }
+        )+
+    };
+}
+
+impl_max_size_tuple!(
+    1 => (A),
+    2 => (A, B),
+    3 => (A, B, C),
+    4 => (A, B, C, D),
+    5 => (A, B, C, D, E),
+    6 => (A, B, C, D, E, F),
+);
+
+impl<const N: usize> MaxSize for str_buf::StrBuf<N> {
+    //Quotes and control characters escaped as `\u00XX`.
+    const MAX_SIZE: usize = 2 + 6 * N;
+}
+
+#[cfg(feature = "heapless")]
+impl<T: MaxSize, const N: usize> MaxSize for heapless::Vec<T, N> {
+    const MAX_SIZE: usize = <[T; N]>::MAX_SIZE;
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> MaxSize for heapless::String<N> {
+    const MAX_SIZE: usize = 2 + 6 * N;
+}
+
+impl MaxSize for Id {
+    const MAX_SIZE: usize = max(u64::MAX_SIZE, crate::id::StrBuf::MAX_SIZE);
+}
+
+impl<T: MaxSize, M: MaxSize> MaxSize for Error<T, M> {
+    const MAX_SIZE: usize = r#"{"code":,"message":,"data":}"#.len() + ErrorCode::MAX_SIZE + M::MAX_SIZE + T::MAX_SIZE;
+}
+
+impl<P: MaxSize, T: MaxSize> MaxSize for Request<P, T> {
+    //JSON-RPCv1 request always contains `params` and `id`, defaulting to `[]` and `null`.
+    const MAX_SIZE: usize = r#"{"jsonrpc":,"method":,"params":,"id":}"#.len() + Version::MAX_SIZE + T::MAX_SIZE + max(P::MAX_SIZE, 2) + max(Id::MAX_SIZE, NULL);
+}
+
+impl<R: MaxSize, E: MaxSize, EM: MaxSize> MaxSize for Response<R, E, EM> {
+    //Upper bound, covering both JSON-RPCv2 and JSON-RPCv1 with `result` and `error` together.
+    const MAX_SIZE: usize = r#"{"jsonrpc":,"result":,"error":,"id":}"#.len() + Version::MAX_SIZE + max(R::MAX_SIZE, NULL) + max(Error::<E, EM>::MAX_SIZE, NULL) + max(Id::MAX_SIZE, NULL);
+}
+
+impl<P: Serialize, T: Serialize> Request<P, T> {
+    #[inline]
+    ///Serializes JSON into `buffer` using `serde-json-core`, returning number of written bytes.
+    pub fn to_slice(&self, buffer: &mut [u8]) -> Result<usize, serde_json_core::ser::Error> {
+        serde_json_core::to_slice(self, buffer)
+    }
+}
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>> Request<P, T> {
+    #[inline]
+    ///Deserializes JSON using `serde-json-core`, returning request and number of consumed bytes.
+    ///
+    ///Note that `serde-json-core` cannot deserialize `Id` unless either `id-number-only` or `id-str-only` feature is enabled.
+    pub fn from_slice(bytes: &'de [u8]) -> Result<(Self, usize), serde_json_core::de::Error> {
+        serde_json_core::from_slice(bytes)
+    }
+}
+
+impl<R: Serialize, E: Serialize, EM: Serialize> Response<R, E, EM> {
+    #[inline]
+    ///Serializes JSON into `buffer` using `serde-json-core`, returning number of written bytes.
+    pub fn to_slice(&self, buffer: &mut [u8]) -> Result<usize, serde_json_core::ser::Error> {
+        serde_json_core::to_slice(self, buffer)
+    }
+}
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> Response<R, E, EM> {
+    #[inline]
+    ///Deserializes JSON using `serde-json-core`, returning response and number of consumed bytes.
+    ///
+    ///Note that `serde-json-core` cannot deserialize `Id` unless either `id-number-only` or `id-str-only` feature is enabled.
+    pub fn from_slice(bytes: &'de [u8]) -> Result<(Self, usize), serde_json_core::de::Error> {
+        serde_json_core::from_slice(bytes)
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::request::{Request, RequestVisitor};
+use crate::response::{Response, ResponseVisitor};
+use crate::message::{Message, MessageVisitor};
+
+use core::marker::PhantomData;
+
+///Wrapper to opt into JSON-RPCv1 detection during deserialization.
+///
+///JSON-RPCv1 has no version member, so message without `jsonrpc` or `version` is treated as `V1` when it has shape of JSON-RPCv1:
+///
+///- `Request` contains `id`, which is always present in JSON-RPCv1, even for notifications;
+///- `Response` contains both `result` and `error`, one of which is `null`.
+///
+///Otherwise, as well as without this wrapper, message without version is treated as `V2`.
+///
+///Outside of this wrapper, JSON-RPCv1.1 `version` member is rejected, as well as `Response` of zero-sized result type containing both `result` and `error`.
+///
+///Serialization is the same as for inner type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Legacy<T>(pub T);
+
+impl_wrapper!(Legacy);
+
+impl<T: Serialize> Serialize for Legacy<T> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(ser)
+    }
+}
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>> Deserialize<'de> for Legacy<Request<P, T>> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        der.deserialize_map(RequestVisitor::<P, T, true>(PhantomData)).map(Legacy)
+    }
+}
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> Deserialize<'de> for Legacy<Response<R, E, EM>> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        der.deserialize_map(ResponseVisitor::<R, E, EM, true>(PhantomData)).map(Legacy)
+    }
+}
+
+impl<'de, P: Deserialize<'de>, R: Deserialize<'de>, E: Deserialize<'de>, T: Deserialize<'de>, EM: Deserialize<'de>> Deserialize<'de> for Legacy<Message<P, R, E, T, EM>> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        der.deserialize_map(MessageVisitor::<P, R, E, T, EM, true>(PhantomData)).map(Legacy)
+    }
+}
@@ -0,0 +1,274 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, Error as _, IgnoredAny, MapAccess, Visitor};
+
+use crate::error::Error;
+use crate::version::Version;
+use crate::id::Id;
+use crate::request::Request;
+use crate::response::Response;
+
+use core::{fmt, mem};
+use core::marker::PhantomData;
+
+///Policy to resolve `Response` that contains both `result` and `error`.
+///
+///Note that `null` is always treated as absence of value, and therefore it is not a conflict.
+pub trait ConflictPolicy {
+    ///Picks either `result` or `error` as payload.
+    ///
+    ///Returning `None` fails deserialization.
+    fn resolve<R, E>(result: R, error: E) -> Option<Result<R, E>>;
+}
+
+///Policy, which uses `error` as payload in case of conflict.
+pub struct PreferError;
+
+impl ConflictPolicy for PreferError {
+    #[inline(always)]
+    fn resolve<R, E>(_: R, error: E) -> Option<Result<R, E>> {
+        Some(Err(error))
+    }
+}
+
+///Policy, which uses `result` as payload in case of conflict.
+pub struct PreferResult;
+
+impl ConflictPolicy for PreferResult {
+    #[inline(always)]
+    fn resolve<R, E>(result: R, _: E) -> Option<Result<R, E>> {
+        Some(Ok(result))
+    }
+}
+
+///Policy, which fails deserialization in case of conflict.
+pub struct RejectConflict;
+
+impl ConflictPolicy for RejectConflict {
+    #[inline(always)]
+    fn resolve<R, E>(_: R, _: E) -> Option<Result<R, E>> {
+        None
+    }
+}
+
+///Wrapper to relax JSON-RPC rules during deserialization, for use with non-compliant peers.
+///
+///On top of regular rules, following is allowed:
+///
+///- Unknown members are ignored;
+///- Member names are matched case-insensitively;
+///- `jsonrpc` member may be omitted and defaults to v2, or replaced with `version`;
+///- `Response` may contain both `result` and `error`, which is resolved using policy `C`.
+///
+///Serialization is the same as for inner type.
+pub struct Lenient<T, C=PreferError>(pub T, PhantomData<C>);
+
+impl_wrapper!(Lenient<C>);
+
+impl<T: Serialize, C> Serialize for Lenient<T, C> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(ser)
+    }
+}
+
+enum RequestKey {
+    JsonRpc,
+    Method,
+    Params,
+    Id,
+    Unknown,
+}
+
+impl<'a> Deserialize<'a> for RequestKey {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'a> Visitor<'a> for KeyVisitor {
+            type Value = RequestKey;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Key must be a string")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+                if text.eq_ignore_ascii_case("jsonrpc") || text.eq_ignore_ascii_case("version") {
+                    Ok(RequestKey::JsonRpc)
+                } else if text.eq_ignore_ascii_case("method") {
+                    Ok(RequestKey::Method)
+                } else if text.eq_ignore_ascii_case("params") {
+                    Ok(RequestKey::Params)
+                } else if text.eq_ignore_ascii_case("id") {
+                    Ok(RequestKey::Id)
+                } else {
+                    Ok(RequestKey::Unknown)
+                }
+            }
+        }
+
+        des.deserialize_identifier(KeyVisitor)
+    }
+}
+
+enum ResponseKey {
+    JsonRpc,
+    Result,
+    Error,
+    Id,
+    Unknown,
+}
+
+impl<'a> Deserialize<'a> for ResponseKey {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'a> Visitor<'a> for KeyVisitor {
+            type Value = ResponseKey;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Key must be a string")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+                if text.eq_ignore_ascii_case("jsonrpc") || text.eq_ignore_ascii_case("version") {
+                    Ok(ResponseKey::JsonRpc)
+                } else if text.eq_ignore_ascii_case("result") {
+                    Ok(ResponseKey::Result)
+                } else if text.eq_ignore_ascii_case("error") {
+                    Ok(ResponseKey::Error)
+                } else if text.eq_ignore_ascii_case("id") {
+                    Ok(ResponseKey::Id)
+                } else {
+                    Ok(ResponseKey::Unknown)
+                }
+            }
+        }
+
+        des.deserialize_identifier(KeyVisitor)
+    }
+}
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>, C> Deserialize<'de> for Lenient<Request<P, T>, C> {
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct MapVisit<P, T, C>(PhantomData<(P, T, C)>);
+
+        impl<'de, P: Deserialize<'de>, T: Deserialize<'de>, C> Visitor<'de> for MapVisit<P, T, C> {
+            type Value = Lenient<Request<P, T>, C>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Object resembling JSON-RPC request type")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut version = None;
+                let mut method = None;
+                let mut params = None;
+                let mut id = None;
+
+                while let Some(key) = map.next_key::<RequestKey>()? {
+                    match key {
+                        RequestKey::JsonRpc => {
+                            version = Some(map.next_value::<Version>()?);
+                        },
+                        RequestKey::Method => {
+                            method = Some(map.next_value::<T>()?);
+                        },
+                        RequestKey::Params => {
+                            params = map.next_value::<Option<P>>()?;
+                        },
+                        RequestKey::Id => {
+                            id = map.next_value::<Option<Id>>()?;
+                        },
+                        RequestKey::Unknown => {
+                            map.next_value::<IgnoredAny>()?;
+                        },
+                    }
+                }
+
+                Ok(Lenient::new(Request {
+                    jsonrpc: version.unwrap_or(Version::V2),
+                    method: match method {
+                        Some(method) => method,
+                        None => return Err(A::Error::missing_field("method")),
+                    },
+                    params,
+                    id,
+                }))
+            }
+        }
+
+        der.deserialize_map(MapVisit(PhantomData))
+    }
+}
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>, C: ConflictPolicy> Deserialize<'de> for Lenient<Response<R, E, EM>, C> {
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct MapVisit<R, E, EM, C>(PhantomData<(R, E, EM, C)>);
+
+        impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>, C: ConflictPolicy> Visitor<'de> for MapVisit<R, E, EM, C> {
+            type Value = Lenient<Response<R, E, EM>, C>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Object resembling JSON-RPC response type")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut version = None;
+                let mut result = None;
+                let mut error = None;
+                let mut id = None;
+
+                while let Some(key) = map.next_key::<ResponseKey>()? {
+                    match key {
+                        ResponseKey::JsonRpc => {
+                            version = Some(map.next_value::<Version>()?);
+                        },
+                        //Zero sized result can be only `null`, so we cannot use `Option`
+                        ResponseKey::Result if mem::size_of::<R>() == 0 => {
+                            result = Some(map.next_value::<R>()?);
+                        },
+                        ResponseKey::Result => {
+                            result = map.next_value::<Option<R>>()?;
+                        },
+                        ResponseKey::Error => {
+                            error = map.next_value::<Option<Error<E, EM>>>()?;
+                        },
+                        ResponseKey::Id => {
+                            id = map.next_value::<Option<Id>>()?;
+                        },
+                        ResponseKey::Unknown => {
+                            map.next_value::<IgnoredAny>()?;
+                        },
+                    }
+                }
+
+                let payload = match (result, error) {
+                    //Zero sized result is always `null`, hence error takes priority.
+                    (Some(_), Some(error)) if mem::size_of::<R>() == 0 => Err(error),
+                    (Some(result), Some(error)) => match C::resolve(result, error) {
+                        Some(payload) => payload,
+                        None => return Err(A::Error::custom("JSON-RPC Response contains both result and error field")),
+                    },
+                    (Some(result), None) => Ok(result),
+                    (None, Some(error)) => Err(error),
+                    (None, None) => return Err(A::Error::custom("JSON-RPC Response is missing either result or error field.")),
+                };
+
+                Ok(Lenient::new(Response {
+                    jsonrpc: version.unwrap_or(Version::V2),
+                    payload,
+                    id,
+                }))
+            }
+        }
+
+        der.deserialize_map(MapVisit(PhantomData))
+    }
+}
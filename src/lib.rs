@@ -7,23 +7,59 @@
 //!
 //! - `id-str-only` - Forces ID deserialization to assume string only.
 //! - `id-number-only` - Forces ID deserialization to assume number only.
+//! - `serde-json-core` - Enables `to_slice`/`from_slice` helpers and `MaxSize` to size static buffers.
+//! - `serde_json` - Enables `parse_request` and `parse_dispatch` to classify invalid requests according to specification, buffering of `params` preceding `method` by `Dispatch` and sorting of params by `Canonical`.
+//! - `alloc` - Enables conversion of `String` into `Id`.
+//! - `uuid` - Enables conversion of `uuid::Uuid` into `Id`.
+//! - `ulid` - Enables conversion of `ulid::Ulid` into `Id`.
+//! - `heapless` - Enables fixed capacity batches and params, backed by `heapless`.
+//! - `framing` - Enables COBS/SLIP framing of messages with optional CRC, to transfer them over serial link. Together with `serde-json-core` enables `to_frame` of `Request` and `Response`.
+//! - `msgpack` - Enables `MsgPackRpc` to bridge msgpack-rpc messages with JSON-RPC messages, which are always `V2`. Pulls no dependency, use any msgpack serde format (e.g. `rmp-serde`) to encode messages.
+//! - `defmt` - Implements `defmt::Format` for messages, to log them on embedded devices.
+//! - `derive` - Enables `rpc` macro to generate typed client and server definitions out of trait.
+//!
+//! ## CBOR
+//!
+//! Messages are not tied to JSON and can be encoded with any self-describing serde format, such as CBOR (e.g. `ciborium`).
+//! Mapping follows JSON representation:
+//!
+//! - Messages and `Error` are maps with text keys, named same as JSON members; absent members are omitted;
+//! - `Version` is text string `"2.0"`, `"1.1"` or `"1.0"`;
+//! - `Id` is unsigned integer or text string, while missing `id` of response is `null`;
+//! - `ErrorCode` is integer;
+//! - `method` and error's `message` are text strings.
+//!
+//! Note that borrowed text types, such as `&str`, cannot be used with decoders that do not borrow from input.
 #![warn(missing_docs)]
 
 #![no_std]
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::style, clippy::derivable_impls))]
 #![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 pub use str_buf;
+#[doc(hidden)]
+pub use serde;
+#[doc(hidden)]
+pub use serde_derive;
+#[cfg(feature = "derive")]
+pub use json_rpc_types_derive::rpc;
+#[cfg(feature = "heapless")]
+pub use heapless;
 
 #[rustfmt::skip]
 mod version;
 pub use version::Version;
 #[rustfmt::skip]
 mod id;
-pub use id::Id;
+pub use id::{Id, IdOnly, ParseIdError};
+#[rustfmt::skip]
+mod text;
+pub use text::FromText;
 #[rustfmt::skip]
 mod error;
-pub use error::{ErrorCode, Error};
+pub use error::{ErrorCode, Error, ClientError, BuildError};
 #[rustfmt::skip]
 mod request;
 pub use request::Request;
@@ -31,4 +67,60 @@ pub use request::Request;
 mod response;
 pub use response::Response;
 #[rustfmt::skip]
+#[macro_use]
 mod utils;
+#[rustfmt::skip]
+mod strict;
+pub use strict::Strict;
+#[rustfmt::skip]
+mod lenient;
+pub use lenient::{Lenient, ConflictPolicy, PreferError, PreferResult, RejectConflict};
+#[rustfmt::skip]
+mod legacy;
+pub use legacy::Legacy;
+#[rustfmt::skip]
+mod ext;
+pub use ext::Extended;
+#[rustfmt::skip]
+mod canonical;
+pub use canonical::{Canonical, CanonicalPolicy, Member, IdFirst, SpecOrder};
+#[rustfmt::skip]
+mod params;
+pub use params::{Params, Args, deserialize_args};
+#[rustfmt::skip]
+mod message;
+pub use message::Message;
+#[rustfmt::skip]
+mod method;
+pub use method::{Method, MethodName};
+#[rustfmt::skip]
+mod dispatch;
+pub use dispatch::Dispatch;
+#[cfg(feature = "heapless")]
+#[rustfmt::skip]
+mod fixed;
+#[cfg(feature = "heapless")]
+pub use fixed::{Batch, RequestBatch, ResponseBatch, ArrayParams};
+#[cfg(feature = "framing")]
+#[rustfmt::skip]
+mod framing;
+#[cfg(feature = "framing")]
+pub use framing::{Framing, Encoding, Checksum, FrameError, FrameDecoder};
+#[cfg(feature = "msgpack")]
+#[rustfmt::skip]
+mod msgpack;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgPackRpc;
+#[cfg(feature = "defmt")]
+#[rustfmt::skip]
+mod format;
+#[cfg(feature = "serde-json-core")]
+#[rustfmt::skip]
+mod json_core;
+#[cfg(feature = "serde-json-core")]
+pub use json_core::MaxSize;
+#[cfg(feature = "serde_json")]
+#[rustfmt::skip]
+mod classify;
+#[cfg(feature = "serde_json")]
+pub use classify::{Rejected, parse_request, parse_dispatch, recover_id};
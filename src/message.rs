@@ -0,0 +1,176 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, MapAccess, Visitor};
+
+use crate::version::Version;
+use crate::id::Id;
+use crate::request::{self, Request};
+use crate::response::{self, Response};
+use crate::ext::Members;
+use crate::utils;
+
+use core::fmt;
+use core::marker::PhantomData;
+
+///Incoming message, which can be either request or response of any protocol version.
+///
+///Deserialization detects kind of message and its version using following rules:
+///
+///- Message with `method` is `Request`, while message with `result` or `error` is `Response`;
+///- Version is taken from `jsonrpc` or `version` member;
+///- Otherwise, when wrapped in `Legacy`, it is `V1` if message has shape of JSON-RPCv1 (i.e. `id` in request, or both `result` and `error` in response);
+///- Otherwise it is `V2`.
+///
+///Detected version is stored within message, so that reply can be sent using the same version.
+///
+///Type parameters:
+///
+///- `P`  - Type of request's `params`.
+///- `R`  - Type of response's `result`.
+///- `E`  - Type of optional data for response's `Error`.
+///- `T`  - Type of request's `method`.
+///- `EM` - Type of message for response's `Error`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message<P, R, E, T=request::StrBuf, EM=crate::error::StrBuf> {
+    ///Request or notification
+    Request(Request<P, T>),
+    ///Response
+    Response(Response<R, E, EM>),
+}
+
+impl<P, R, E, T, EM> Message<P, R, E, T, EM> {
+    #[inline]
+    ///Returns protocol version of the message.
+    pub const fn version(&self) -> Version {
+        match self {
+            Message::Request(req) => req.jsonrpc,
+            Message::Response(res) => res.jsonrpc,
+        }
+    }
+
+    #[inline]
+    ///Returns message's identifier, if any.
+    pub const fn id(&self) -> Option<&Id> {
+        match self {
+            Message::Request(req) => req.id.as_ref(),
+            Message::Response(res) => res.id.as_ref(),
+        }
+    }
+}
+
+impl<P: Serialize, R: Serialize, E: Serialize, T: Serialize, EM: Serialize> Serialize for Message<P, R, E, T, EM> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Message::Request(req) => req.serialize(ser),
+            Message::Response(res) => res.serialize(ser),
+        }
+    }
+}
+
+enum Key {
+    JsonRpc,
+    Version,
+    Request(request::Key),
+    Response(utils::Key),
+    Id,
+}
+
+struct MessageMembers<P, R, E, T, EM> {
+    request: request::Members<P, T>,
+    response: response::Members<R, E, EM>,
+    has_version: bool,
+    has_id: bool,
+    legacy: bool,
+}
+
+impl<P, R, E, T, EM> MessageMembers<P, R, E, T, EM> {
+    fn finish<ERR: de::Error>(self) -> Result<Message<P, R, E, T, EM>, ERR> {
+        match (self.request.is_empty(), self.response.is_empty()) {
+            (false, true) => self.request.finish().map(Message::Request),
+            (true, false) => self.response.finish().map(Message::Response),
+            (false, false) => Err(de::Error::custom("JSON-RPC message contains both request and response fields")),
+            (true, true) => Err(de::Error::custom("JSON-RPC message is missing method, result or error field")),
+        }
+    }
+}
+
+impl<'de, P: Deserialize<'de>, R: Deserialize<'de>, E: Deserialize<'de>, T: Deserialize<'de>, EM: Deserialize<'de>> Members<'de> for MessageMembers<P, R, E, T, EM> {
+    type Key = Key;
+    const NAMES: &'static [&'static str] = &["jsonrpc", "version", "method", "params", "result", "error", "id"];
+
+    #[inline]
+    fn key(text: &str) -> Option<Self::Key> {
+        match request::Members::<P, T>::key(text) {
+            Some(request::Key::JsonRpc) => Some(Key::JsonRpc),
+            Some(request::Key::Version) => Some(Key::Version),
+            Some(request::Key::Id) => Some(Key::Id),
+            Some(key) => Some(Key::Request(key)),
+            None => match utils::Key::from_str(text) {
+                Some(utils::Key::JsonRpc) => Some(Key::JsonRpc),
+                Some(utils::Key::Version) => Some(Key::Version),
+                Some(utils::Key::Id) => Some(Key::Id),
+                Some(key) => Some(Key::Response(key)),
+                None => None,
+            },
+        }
+    }
+
+    fn visit_value<A: MapAccess<'de>>(&mut self, key: Self::Key, map: &mut A) -> Result<(), A::Error> {
+        match key {
+            Key::Version if !self.legacy => Err(utils::legacy_version()),
+            Key::JsonRpc | Key::Version if self.has_version => Err(de::Error::duplicate_field("jsonrpc")),
+            Key::JsonRpc | Key::Version => {
+                let version = map.next_value::<Version>()?;
+                self.has_version = true;
+                self.request.set_version(version);
+                self.response.set_version(version);
+                Ok(())
+            },
+            Key::Id if self.has_id => Err(de::Error::duplicate_field("id")),
+            Key::Id => {
+                let id = map.next_value::<Option<Id>>()?;
+                self.has_id = true;
+                self.request.set_id(id.clone());
+                self.response.set_id(id);
+                Ok(())
+            },
+            Key::Request(key) => self.request.visit_value(key, map),
+            Key::Response(key) => self.response.visit_value(key, map),
+        }
+    }
+}
+
+///Visitor of message, which infers `V1` from message's shape when `INFER_V1` is set.
+pub(crate) struct MessageVisitor<P, R, E, T, EM, const INFER_V1: bool>(pub(crate) PhantomData<(P, R, E, T, EM)>);
+
+impl<'de, P: Deserialize<'de>, R: Deserialize<'de>, E: Deserialize<'de>, T: Deserialize<'de>, EM: Deserialize<'de>, const INFER_V1: bool> Visitor<'de> for MessageVisitor<P, R, E, T, EM, INFER_V1> {
+    type Value = Message<P, R, E, T, EM>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Object resembling JSON-RPC request or response type")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut members = MessageMembers {
+            request: request::Members::new(),
+            response: response::Members::new(),
+            has_version: false,
+            has_id: false,
+            legacy: INFER_V1,
+        };
+        if INFER_V1 {
+            members.request.legacy();
+            members.response.legacy();
+        }
+        crate::ext::visit_extensions::<_, _, ()>(&mut map, &mut members)?;
+        members.finish()
+    }
+}
+
+impl<'de, P: Deserialize<'de>, R: Deserialize<'de>, E: Deserialize<'de>, T: Deserialize<'de>, EM: Deserialize<'de>> Deserialize<'de> for Message<P, R, E, T, EM> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        der.deserialize_map(MessageVisitor::<P, R, E, T, EM, false>(PhantomData))
+    }
+}
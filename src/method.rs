@@ -0,0 +1,188 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, Visitor};
+
+use crate::version::Version;
+use crate::id::Id;
+use crate::error::{Error, ErrorCode};
+use crate::request::{self, Request};
+use crate::response::Response;
+
+use core::fmt;
+use core::marker::PhantomData;
+
+///Typed definition of JSON-RPC method, shared between client and server.
+///
+///Provided helpers build and decode messages using method's types, guaranteeing that both sides
+///agree on shape of `params`, `result` and `error`.
+///
+///```rust
+///use json_rpc_types::{Method, Id};
+///
+///struct Subtract;
+///
+///impl Method for Subtract {
+///    const NAME: &'static str = "subtract";
+///    type Params = [i64; 2];
+///    type Result = i64;
+///    type Error = ();
+///}
+///
+///let request = Subtract::request([42, 23], Id::Num(1));
+///assert_eq!(request.method, "subtract");
+///```
+pub trait Method {
+    ///Name of the method, used as `method` member of request.
+    ///
+    ///Must fit default capacity of `Request`'s method (32 bytes) in order to use `decode_request`,
+    ///which is checked at compile time.
+    const NAME: &'static str;
+    ///Type of request's `params`.
+    type Params;
+    ///Type of successful response's `result`.
+    type Result;
+    ///Type of optional data of response's `error`.
+    type Error;
+
+    #[inline]
+    ///Creates JSON-RPCv2 request, which expects response.
+    fn request(params: Self::Params, id: Id) -> Request<Self::Params, &'static str> {
+        Request {
+            jsonrpc: Version::V2,
+            method: Self::NAME,
+            params: Some(params),
+            id: Some(id),
+        }
+    }
+
+    #[inline]
+    ///Creates JSON-RPCv2 notification.
+    fn notification(params: Self::Params) -> Request<Self::Params, &'static str> {
+        Request {
+            jsonrpc: Version::V2,
+            method: Self::NAME,
+            params: Some(params),
+            id: None,
+        }
+    }
+
+    #[inline]
+    ///Returns whether `request` invokes this method.
+    fn matches<P, T: AsRef<str>>(request: &Request<P, T>) -> bool {
+        request.method.as_ref() == Self::NAME
+    }
+
+    #[inline]
+    ///Creates successful JSON-RPCv2 response.
+    fn result(result: Self::Result, id: Option<Id>) -> Response<Self::Result, Self::Error> {
+        Response::result(Version::V2, result, id)
+    }
+
+    #[inline]
+    ///Creates error JSON-RPCv2 response.
+    fn error(error: Error<Self::Error>, id: Option<Id>) -> Response<Self::Result, Self::Error> {
+        Response::error(Version::V2, error, id)
+    }
+
+    ///Decodes request, failing if its `method` is not this method.
+    fn decode_request<'de, D: Deserializer<'de>>(der: D) -> Result<Request<Self::Params>, D::Error> where Self::Params: Deserialize<'de> {
+        let () = NameCapacity::<Self>::CHECK;
+
+        let request = Request::<Self::Params>::deserialize(der)?;
+        match Self::matches(&request) {
+            true => Ok(request),
+            false => Err(de::Error::invalid_value(de::Unexpected::Str(request.method.as_str()), &Self::NAME)),
+        }
+    }
+
+    #[inline]
+    ///Decodes response to this method.
+    fn decode_response<'de, D: Deserializer<'de>>(der: D) -> Result<Response<Self::Result, Self::Error>, D::Error> where Self::Result: Deserialize<'de>, Self::Error: Deserialize<'de> {
+        Response::deserialize(der)
+    }
+}
+
+///Compile time check that `M::NAME` fits `Request`'s default method.
+struct NameCapacity<M: ?Sized>(PhantomData<M>);
+
+impl<M: Method + ?Sized> NameCapacity<M> {
+    const CHECK: () = assert!(M::NAME.len() <= request::StrBuf::capacity(), "Method::NAME exceeds capacity of Request's method");
+}
+
+///Method name, which tolerates names exceeding capacity of `N` bytes.
+///
+///Unlike `StrBuf`, it doesn't fail deserialization of overlong name, so that request can be
+///replied with `MethodNotFound` error, as no method can have such name.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MethodName<const N: usize = 32> {
+    ///Method name.
+    Name(str_buf::StrBuf<N>),
+    ///Method name, that exceeded capacity.
+    Overlong,
+}
+
+impl<const N: usize> MethodName<N> {
+    #[inline]
+    ///Returns method name, if it fits capacity.
+    pub const fn as_str(&self) -> Option<&str> {
+        match self {
+            MethodName::Name(name) => Some(name.as_str()),
+            MethodName::Overlong => None,
+        }
+    }
+}
+
+impl<const N: usize> Serialize for MethodName<N> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MethodName::Name(name) => ser.serialize_str(name.as_str()),
+            MethodName::Overlong => Err(serde::ser::Error::custom("Method name exceeds capacity")),
+        }
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for MethodName<N> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct NameVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for NameVisitor<N> {
+            type Value = MethodName<N>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+                match str_buf::StrBuf::from_str_checked(text) {
+                    Ok(name) => Ok(MethodName::Name(name)),
+                    Err(_) => Ok(MethodName::Overlong),
+                }
+            }
+        }
+
+        der.deserialize_str(NameVisitor)
+    }
+}
+
+impl<P, const N: usize> Request<P, MethodName<N>> {
+    ///Returns request with method name, or `MethodNotFound` error response if name is overlong.
+    ///
+    ///In case of notification, there should be no response, therefore error is `None`.
+    pub fn into_method<R, E>(self) -> Result<Request<P, str_buf::StrBuf<N>>, Option<Response<R, E>>> {
+        match self.method {
+            MethodName::Name(method) => Ok(Request {
+                jsonrpc: self.jsonrpc,
+                method,
+                params: self.params,
+                id: self.id,
+            }),
+            MethodName::Overlong => match self.id {
+                Some(id) => Err(Some(Response::error(self.jsonrpc, Error::from_code(ErrorCode::MethodNotFound), Some(id)))),
+                None => Err(None),
+            },
+        }
+    }
+}
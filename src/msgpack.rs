@@ -0,0 +1,550 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::de::value::{BorrowedStrDeserializer, MapAccessDeserializer, StrDeserializer};
+use serde::ser::SerializeSeq;
+
+use crate::version::Version;
+use crate::id::Id;
+use crate::error::{Error, ErrorCode};
+use crate::request::{self, Request};
+use crate::response::Response;
+use crate::message::Message;
+
+use core::convert::TryFrom;
+use core::fmt;
+use core::marker::PhantomData;
+
+const TYPE_REQUEST: u8 = 0;
+const TYPE_RESPONSE: u8 = 1;
+const TYPE_NOTIFICATION: u8 = 2;
+
+///Message of msgpack-rpc, represented as array.
+///
+///- Request - `[0, msgid, method, params]`;
+///- Response - `[1, msgid, error, result]`, where either `error` or `result` is `nil`;
+///- Notification - `[2, method, params]`.
+///
+///Missing `params` are serialized as empty array.
+///Error is represented as JSON-RPC `Error` object, so that the same handlers can serve both protocols.
+///
+///As msgpack-rpc doesn't specify format of error, any value other than `nil` is accepted as error:
+///
+///- Map is deserialized as JSON-RPC `Error` object;
+///- String becomes `message` of error with `ServerError(-32000)` code;
+///- Array, such as `[type, "message"]` used by Neovim, becomes error with its first element as code,
+///  if it is integer, and second element as `message`, if it is string;
+///- Any other value becomes error with `ServerError(-32000)` code.
+///
+///When `message` is not provided, it is default message of code.
+///`data` is only set by JSON-RPC `Error` object.
+///
+///Converting from JSON-RPC messages requires `id` to be number, fitting `u32`, otherwise original message is returned.
+///Converting into JSON-RPC messages always produces `V2` messages, as msgpack-rpc has no version.
+///
+///Note that `rmp_serde::to_vec` encodes `Error` as array, use `rmp_serde::to_vec_named` to encode it as map.
+///
+///Type parameters:
+///
+///- `P`  - Type of request's `params`.
+///- `R`  - Type of response's `result`.
+///- `E`  - Type of optional data for response's `Error`.
+///- `T`  - Type of request's `method`.
+///- `EM` - Type of message for response's `Error`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MsgPackRpc<P, R, E, T=request::StrBuf, EM=crate::error::StrBuf> {
+    ///Request, expecting response.
+    Request {
+        ///Identifier of request.
+        msgid: u32,
+        ///Name of method.
+        method: T,
+        ///Method's parameters.
+        params: Option<P>,
+    },
+    ///Response to request.
+    Response {
+        ///Identifier of request.
+        msgid: u32,
+        ///Result of method.
+        payload: Result<R, Error<E, EM>>,
+    },
+    ///Notification, which has no response.
+    Notification {
+        ///Name of method.
+        method: T,
+        ///Method's parameters.
+        params: Option<P>,
+    },
+}
+
+impl<P, R, E, T, EM> MsgPackRpc<P, R, E, T, EM> {
+    #[inline]
+    ///Returns `msgid` of message, if any.
+    pub const fn msgid(&self) -> Option<u32> {
+        match self {
+            MsgPackRpc::Request { msgid, .. } => Some(*msgid),
+            MsgPackRpc::Response { msgid, .. } => Some(*msgid),
+            MsgPackRpc::Notification { .. } => None,
+        }
+    }
+}
+
+#[inline]
+fn to_msgid(id: &Id) -> Option<u32> {
+    match id {
+        Id::Num(id) => u32::try_from(*id).ok(),
+        Id::Str(_) => None,
+    }
+}
+
+impl<P, R, E, T, EM> TryFrom<Request<P, T>> for MsgPackRpc<P, R, E, T, EM> {
+    type Error = Request<P, T>;
+
+    fn try_from(request: Request<P, T>) -> Result<Self, Self::Error> {
+        match request.id {
+            Some(ref id) => match to_msgid(id) {
+                Some(msgid) => Ok(MsgPackRpc::Request {
+                    msgid,
+                    method: request.method,
+                    params: request.params,
+                }),
+                None => Err(request),
+            },
+            None => Ok(MsgPackRpc::Notification {
+                method: request.method,
+                params: request.params,
+            }),
+        }
+    }
+}
+
+impl<P, R, E, T, EM> TryFrom<Response<R, E, EM>> for MsgPackRpc<P, R, E, T, EM> {
+    type Error = Response<R, E, EM>;
+
+    fn try_from(response: Response<R, E, EM>) -> Result<Self, Self::Error> {
+        match response.id.as_ref().and_then(to_msgid) {
+            Some(msgid) => Ok(MsgPackRpc::Response {
+                msgid,
+                payload: response.payload,
+            }),
+            None => Err(response),
+        }
+    }
+}
+
+impl<P, R, E, T, EM> TryFrom<Message<P, R, E, T, EM>> for MsgPackRpc<P, R, E, T, EM> {
+    type Error = Message<P, R, E, T, EM>;
+
+    #[inline]
+    fn try_from(message: Message<P, R, E, T, EM>) -> Result<Self, Self::Error> {
+        match message {
+            Message::Request(request) => Self::try_from(request).map_err(Message::Request),
+            Message::Response(response) => Self::try_from(response).map_err(Message::Response),
+        }
+    }
+}
+
+impl<P, R, E, T, EM> From<MsgPackRpc<P, R, E, T, EM>> for Message<P, R, E, T, EM> {
+    fn from(message: MsgPackRpc<P, R, E, T, EM>) -> Self {
+        match message {
+            MsgPackRpc::Request { msgid, method, params } => Message::Request(Request {
+                jsonrpc: Version::V2,
+                method,
+                params,
+                id: Some(Id::Num(msgid.into())),
+            }),
+            MsgPackRpc::Response { msgid, payload } => Message::Response(Response {
+                jsonrpc: Version::V2,
+                payload,
+                id: Some(Id::Num(msgid.into())),
+            }),
+            MsgPackRpc::Notification { method, params } => Message::Request(Request {
+                jsonrpc: Version::V2,
+                method,
+                params,
+                id: None,
+            }),
+        }
+    }
+}
+
+///Serializes missing params as empty array.
+struct ParamsRef<'a, P>(&'a Option<P>);
+
+impl<P: Serialize> Serialize for ParamsRef<'_, P> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Some(params) => params.serialize(ser),
+            None => ser.serialize_seq(Some(0))?.end(),
+        }
+    }
+}
+
+impl<P: Serialize, R: Serialize, E: Serialize, T: Serialize, EM: Serialize> Serialize for MsgPackRpc<P, R, E, T, EM> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MsgPackRpc::Request { msgid, method, params } => {
+                let mut state = ser.serialize_seq(Some(4))?;
+                state.serialize_element(&TYPE_REQUEST)?;
+                state.serialize_element(msgid)?;
+                state.serialize_element(method)?;
+                state.serialize_element(&ParamsRef(params))?;
+                state.end()
+            },
+            MsgPackRpc::Response { msgid, payload } => {
+                let mut state = ser.serialize_seq(Some(4))?;
+                state.serialize_element(&TYPE_RESPONSE)?;
+                state.serialize_element(msgid)?;
+                match payload {
+                    Ok(result) => {
+                        state.serialize_element(&())?;
+                        state.serialize_element(result)?;
+                    },
+                    Err(error) => {
+                        state.serialize_element(error)?;
+                        state.serialize_element(&())?;
+                    },
+                }
+                state.end()
+            },
+            MsgPackRpc::Notification { method, params } => {
+                let mut state = ser.serialize_seq(Some(3))?;
+                state.serialize_element(&TYPE_NOTIFICATION)?;
+                state.serialize_element(method)?;
+                state.serialize_element(&ParamsRef(params))?;
+                state.end()
+            },
+        }
+    }
+}
+
+///Code of error, that is not JSON-RPC `Error` object.
+const DEFAULT_CODE: ErrorCode = ErrorCode::ServerError(-32000);
+
+///Builds error, converting `message` into `EM`.
+#[inline]
+fn text_error<'de, E, EM: Deserialize<'de>, ERR: de::Error>(code: ErrorCode, message: Option<EM>) -> Result<Error<E, EM>, ERR> {
+    let message = match message {
+        Some(message) => message,
+        None => EM::deserialize(StrDeserializer::<ERR>::new(code.message()))?,
+    };
+    Ok(Error {
+        code,
+        message,
+        data: None,
+    })
+}
+
+///Error slot of response, accepting any value.
+struct ErrorSlot<E, EM>(PhantomData<(E, EM)>);
+
+impl<'de, E: Deserialize<'de>, EM: Deserialize<'de>> DeserializeSeed<'de> for ErrorSlot<E, EM> {
+    type Value = Option<Error<E, EM>>;
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(self, der: D) -> Result<Self::Value, D::Error> {
+        der.deserialize_any(self)
+    }
+}
+
+impl<'de, E: Deserialize<'de>, EM: Deserialize<'de>> Visitor<'de> for ErrorSlot<E, EM> {
+    type Value = Option<Error<E, EM>>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("msgpack-rpc error")
+    }
+
+    #[inline]
+    fn visit_unit<ERR: de::Error>(self) -> Result<Self::Value, ERR> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_none<ERR: de::Error>(self) -> Result<Self::Value, ERR> {
+        Ok(None)
+    }
+
+    #[inline]
+    fn visit_some<D: Deserializer<'de>>(self, der: D) -> Result<Self::Value, D::Error> {
+        der.deserialize_any(self)
+    }
+
+    #[inline]
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        Error::deserialize(MapAccessDeserializer::new(map)).map(Some)
+    }
+
+    #[inline]
+    fn visit_str<ERR: de::Error>(self, text: &str) -> Result<Self::Value, ERR> {
+        let message = EM::deserialize(StrDeserializer::<ERR>::new(text))?;
+        text_error(DEFAULT_CODE, Some(message)).map(Some)
+    }
+
+    #[inline]
+    fn visit_borrowed_str<ERR: de::Error>(self, text: &'de str) -> Result<Self::Value, ERR> {
+        let message = EM::deserialize(BorrowedStrDeserializer::<ERR>::new(text))?;
+        text_error(DEFAULT_CODE, Some(message)).map(Some)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let code = match seq.next_element::<MaybeCode>()? {
+            Some(MaybeCode(Some(code))) => ErrorCode::from_code(code),
+            _ => DEFAULT_CODE,
+        };
+        let message = match seq.next_element::<MaybeText<EM>>()? {
+            Some(MaybeText(message)) => message,
+            None => None,
+        };
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+        }
+        text_error(code, message).map(Some)
+    }
+
+    #[inline]
+    fn visit_bool<ERR: de::Error>(self, _: bool) -> Result<Self::Value, ERR> {
+        text_error(DEFAULT_CODE, None).map(Some)
+    }
+
+    #[inline]
+    fn visit_i64<ERR: de::Error>(self, _: i64) -> Result<Self::Value, ERR> {
+        text_error(DEFAULT_CODE, None).map(Some)
+    }
+
+    #[inline]
+    fn visit_u64<ERR: de::Error>(self, _: u64) -> Result<Self::Value, ERR> {
+        text_error(DEFAULT_CODE, None).map(Some)
+    }
+
+    #[inline]
+    fn visit_f64<ERR: de::Error>(self, _: f64) -> Result<Self::Value, ERR> {
+        text_error(DEFAULT_CODE, None).map(Some)
+    }
+
+    #[inline]
+    fn visit_bytes<ERR: de::Error>(self, _: &[u8]) -> Result<Self::Value, ERR> {
+        text_error(DEFAULT_CODE, None).map(Some)
+    }
+}
+
+///Accepts any value, producing code only if value is integer.
+struct MaybeCode(Option<i64>);
+
+impl<'de> Deserialize<'de> for MaybeCode {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct CodeVisitor;
+
+        impl<'de> Visitor<'de> for CodeVisitor {
+            type Value = MaybeCode;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Any value")
+            }
+
+            #[inline]
+            fn visit_i64<E: de::Error>(self, code: i64) -> Result<Self::Value, E> {
+                Ok(MaybeCode(Some(code)))
+            }
+
+            #[inline]
+            fn visit_u64<E: de::Error>(self, code: u64) -> Result<Self::Value, E> {
+                Ok(MaybeCode(i64::try_from(code).ok()))
+            }
+
+            #[inline]
+            fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+                Ok(MaybeCode(None))
+            }
+
+            #[inline]
+            fn visit_f64<E: de::Error>(self, _: f64) -> Result<Self::Value, E> {
+                Ok(MaybeCode(None))
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(MaybeCode(None))
+            }
+
+            #[inline]
+            fn visit_bytes<E: de::Error>(self, _: &[u8]) -> Result<Self::Value, E> {
+                Ok(MaybeCode(None))
+            }
+
+            #[inline]
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(MaybeCode(None))
+            }
+
+            #[inline]
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(MaybeCode(None))
+            }
+
+            #[inline]
+            fn visit_some<D: Deserializer<'de>>(self, der: D) -> Result<Self::Value, D::Error> {
+                der.deserialize_any(self)
+            }
+
+            #[inline]
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                IgnoredAny.visit_seq(seq).map(|_| MaybeCode(None))
+            }
+
+            #[inline]
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                IgnoredAny.visit_map(map).map(|_| MaybeCode(None))
+            }
+        }
+
+        der.deserialize_any(CodeVisitor)
+    }
+}
+
+///Accepts any value, producing message only if value is string.
+struct MaybeText<EM>(Option<EM>);
+
+impl<'de, EM: Deserialize<'de>> Deserialize<'de> for MaybeText<EM> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct TextVisitor<EM>(PhantomData<EM>);
+
+        impl<'de, EM: Deserialize<'de>> Visitor<'de> for TextVisitor<EM> {
+            type Value = MaybeText<EM>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Any value")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+                EM::deserialize(StrDeserializer::<E>::new(text)).map(|text| MaybeText(Some(text)))
+            }
+
+            #[inline]
+            fn visit_borrowed_str<E: de::Error>(self, text: &'de str) -> Result<Self::Value, E> {
+                EM::deserialize(BorrowedStrDeserializer::<E>::new(text)).map(|text| MaybeText(Some(text)))
+            }
+
+            #[inline]
+            fn visit_bool<E: de::Error>(self, _: bool) -> Result<Self::Value, E> {
+                Ok(MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_i64<E: de::Error>(self, _: i64) -> Result<Self::Value, E> {
+                Ok(MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_u64<E: de::Error>(self, _: u64) -> Result<Self::Value, E> {
+                Ok(MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_f64<E: de::Error>(self, _: f64) -> Result<Self::Value, E> {
+                Ok(MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_bytes<E: de::Error>(self, _: &[u8]) -> Result<Self::Value, E> {
+                Ok(MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_some<D: Deserializer<'de>>(self, der: D) -> Result<Self::Value, D::Error> {
+                der.deserialize_any(self)
+            }
+
+            #[inline]
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                IgnoredAny.visit_seq(seq).map(|_| MaybeText(None))
+            }
+
+            #[inline]
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                IgnoredAny.visit_map(map).map(|_| MaybeText(None))
+            }
+        }
+
+        der.deserialize_any(TextVisitor(PhantomData))
+    }
+}
+
+struct MsgPackRpcVisitor<P, R, E, T, EM>(PhantomData<(P, R, E, T, EM)>);
+
+#[inline]
+fn next<'de, A: SeqAccess<'de>, V: Deserialize<'de>>(seq: &mut A, idx: usize, exp: &dyn de::Expected) -> Result<V, A::Error> {
+    match seq.next_element()? {
+        Some(value) => Ok(value),
+        None => Err(de::Error::invalid_length(idx, exp)),
+    }
+}
+
+impl<'de, P: Deserialize<'de>, R: Deserialize<'de>, E: Deserialize<'de>, T: Deserialize<'de>, EM: Deserialize<'de>> Visitor<'de> for MsgPackRpcVisitor<P, R, E, T, EM> {
+    type Value = MsgPackRpc<P, R, E, T, EM>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("msgpack-rpc message array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let result = match next::<_, u8>(&mut seq, 0, &self)? {
+            TYPE_REQUEST => MsgPackRpc::Request {
+                msgid: next(&mut seq, 1, &self)?,
+                method: next(&mut seq, 2, &self)?,
+                params: Some(next(&mut seq, 3, &self)?),
+            },
+            TYPE_RESPONSE => {
+                let msgid = next(&mut seq, 1, &self)?;
+                let payload = match seq.next_element_seed(ErrorSlot(PhantomData))? {
+                    Some(error) => error,
+                    None => return Err(de::Error::invalid_length(2, &self)),
+                };
+                let payload = match payload {
+                    Some(error) => {
+                        next::<_, IgnoredAny>(&mut seq, 3, &self)?;
+                        Err(error)
+                    },
+                    None => Ok(next(&mut seq, 3, &self)?),
+                };
+                MsgPackRpc::Response {
+                    msgid,
+                    payload,
+                }
+            },
+            TYPE_NOTIFICATION => MsgPackRpc::Notification {
+                method: next(&mut seq, 1, &self)?,
+                params: Some(next(&mut seq, 2, &self)?),
+            },
+            other => return Err(de::Error::invalid_value(de::Unexpected::Unsigned(other.into()), &"message type 0, 1 or 2")),
+        };
+
+        match seq.next_element::<IgnoredAny>()? {
+            Some(_) => Err(de::Error::custom("msgpack-rpc message contains extra elements")),
+            None => Ok(result),
+        }
+    }
+}
+
+impl<'de, P: Deserialize<'de>, R: Deserialize<'de>, E: Deserialize<'de>, T: Deserialize<'de>, EM: Deserialize<'de>> Deserialize<'de> for MsgPackRpc<P, R, E, T, EM> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        der.deserialize_seq(MsgPackRpcVisitor(PhantomData))
+    }
+}
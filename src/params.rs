@@ -0,0 +1,400 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, DeserializeSeed, IgnoredAny, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::de::value::{BorrowedStrDeserializer, MapAccessDeserializer, MapDeserializer, SeqAccessDeserializer};
+
+use crate::error::ErrorCode;
+
+use core::fmt;
+use core::marker::PhantomData;
+
+///Structured value of `params`, as required by specification.
+///
+///Deserialization rejects anything that is not array or object.
+///
+///Type parameters:
+///
+///- `A` - Type of positional parameters, which is represented as array.
+///- `O` - Type of named parameters, which is represented as object.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Params<A, O> {
+    ///Parameters by position.
+    Positional(A),
+    ///Parameters by name.
+    Named(O),
+}
+
+impl<A, O> Params<A, O> {
+    #[inline(always)]
+    ///Returns whether params are positional.
+    pub const fn is_positional(&self) -> bool {
+        match self {
+            Params::Positional(_) => true,
+            Params::Named(_) => false,
+        }
+    }
+
+    ///Converts params into named form.
+    ///
+    ///Positional parameters are named using `fields`, in the same order as they are declared.
+    ///Missing trailing parameters are treated as missing fields, while excessive parameters result in error.
+    pub fn into_named<'de, V: IntoDeserializer<'de, E>, E: de::Error>(self, fields: &'static [&'static str]) -> Result<O, E> where A: IntoIterator<Item = V>, O: Deserialize<'de> {
+        match self {
+            Params::Positional(params) => {
+                let mut params = params.into_iter();
+                let named = O::deserialize(MapDeserializer::new(fields.iter().copied().zip(&mut params)))?;
+
+                match params.next() {
+                    Some(_) => Err(de::Error::custom(format_args!("{}: expected at most {} parameters", ErrorCode::InvalidParams.message(), fields.len()))),
+                    None => Ok(named),
+                }
+            },
+            Params::Named(params) => Ok(params),
+        }
+    }
+}
+
+impl<A: Serialize, O: Serialize> Serialize for Params<A, O> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Params::Positional(params) => params.serialize(ser),
+            Params::Named(params) => params.serialize(ser),
+        }
+    }
+}
+
+struct ParamsVisitor<A, O>(PhantomData<(A, O)>);
+
+impl<'a, A: Deserialize<'a>, O: Deserialize<'a>> Visitor<'a> for ParamsVisitor<A, O> {
+    type Value = Params<A, O>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_fmt(format_args!("{}: must be either array or object", ErrorCode::InvalidParams.message()))
+    }
+
+    #[inline]
+    fn visit_seq<S: SeqAccess<'a>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        A::deserialize(SeqAccessDeserializer::new(seq)).map(Params::Positional)
+    }
+
+    #[inline]
+    fn visit_map<M: MapAccess<'a>>(self, map: M) -> Result<Self::Value, M::Error> {
+        O::deserialize(MapAccessDeserializer::new(map)).map(Params::Named)
+    }
+}
+
+impl<'a, A: Deserialize<'a>, O: Deserialize<'a>> Deserialize<'a> for Params<A, O> {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        des.deserialize_any(ParamsVisitor(PhantomData))
+    }
+}
+
+///Arguments of method call, that can be passed either by position or by name.
+///
+///Wraps struct or tuple `T`, allowing its deserialization from both `[1,2]` and `{"a":1,"b":2}`.
+///Positional arguments are matched to struct's fields in order of their declaration.
+///
+///Trailing arguments may be omitted, in which case they are deserialized as missing.
+///Note that only `Option` can be omitted, as `#[serde(default)]` is not visible to the deserializer.
+///
+///Failure to deserialize positional argument reports its index and name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Args<T>(pub T);
+
+impl_wrapper!(Args);
+
+impl<T: Serialize> Serialize for Args<T> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(ser)
+    }
+}
+
+impl<'a, T: Deserialize<'a>> Deserialize<'a> for Args<T> {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        deserialize_args(des).map(Args)
+    }
+}
+
+///Deserializes arguments `T` from either positional or named form.
+///
+///Same as `Args`, but can be used with `#[serde(deserialize_with = "...")]`.
+#[inline]
+pub fn deserialize_args<'a, D: Deserializer<'a>, T: Deserialize<'a>>(des: D) -> Result<T, D::Error> {
+    T::deserialize(ArgsDeserializer(des))
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident)*) => {
+        $(
+            #[inline(always)]
+            fn $method<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.0.$method(visitor)
+            }
+        )*
+    };
+}
+
+///Deserializer, that allows struct and tuple to be deserialized from either array or object.
+struct ArgsDeserializer<D>(D);
+
+impl<'a, D: Deserializer<'a>> Deserializer<'a> for ArgsDeserializer<D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64
+        deserialize_char deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_unit deserialize_seq deserialize_map deserialize_identifier deserialize_ignored_any
+    }
+
+    #[inline(always)]
+    fn deserialize_unit_struct<V: Visitor<'a>>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_unit_struct(name, visitor)
+    }
+
+    #[inline(always)]
+    fn deserialize_newtype_struct<V: Visitor<'a>>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_newtype_struct(name, visitor)
+    }
+
+    #[inline(always)]
+    fn deserialize_enum<V: Visitor<'a>>(self, name: &'static str, variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    #[inline]
+    fn deserialize_tuple<V: Visitor<'a>>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_seq(TupleArgsVisitor {
+            len,
+            visitor,
+        })
+    }
+
+    #[inline]
+    fn deserialize_tuple_struct<V: Visitor<'a>>(self, _: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    #[inline]
+    fn deserialize_struct<V: Visitor<'a>>(self, _: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_any(StructArgsVisitor {
+            fields,
+            visitor,
+        })
+    }
+
+    #[inline(always)]
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+struct TupleArgsVisitor<V> {
+    len: usize,
+    visitor: V,
+}
+
+impl<'a, V: Visitor<'a>> Visitor<'a> for TupleArgsVisitor<V> {
+    type Value = V::Value;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_fmt(format_args!("array of at most {} arguments", self.len))
+    }
+
+    fn visit_seq<S: SeqAccess<'a>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        let mut args = PositionalArgs {
+            seq,
+            fields: &[],
+            len: self.len,
+            idx: 0,
+            is_done: false,
+        };
+        let value = self.visitor.visit_seq(&mut args)?;
+        args.end()?;
+        Ok(value)
+    }
+}
+
+struct StructArgsVisitor<V> {
+    fields: &'static [&'static str],
+    visitor: V,
+}
+
+impl<'a, V: Visitor<'a>> Visitor<'a> for StructArgsVisitor<V> {
+    type Value = V::Value;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("array or object of arguments")
+    }
+
+    fn visit_seq<S: SeqAccess<'a>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        let mut args = PositionalArgs {
+            seq,
+            fields: self.fields,
+            len: self.fields.len(),
+            idx: 0,
+            is_done: false,
+        };
+        let value = self.visitor.visit_map(&mut args)?;
+        args.end()?;
+        Ok(value)
+    }
+
+    #[inline(always)]
+    fn visit_map<M: MapAccess<'a>>(self, map: M) -> Result<Self::Value, M::Error> {
+        self.visitor.visit_map(map)
+    }
+}
+
+///Access to positional arguments, which pads missing trailing arguments.
+///
+///When `fields` are present, arguments are accessed as map.
+struct PositionalArgs<S> {
+    seq: S,
+    fields: &'static [&'static str],
+    len: usize,
+    idx: usize,
+    is_done: bool,
+}
+
+impl<'a, S: SeqAccess<'a>> PositionalArgs<S> {
+    fn end(&mut self) -> Result<(), S::Error> {
+        if !self.is_done && self.seq.next_element::<IgnoredAny>()?.is_some() {
+            Err(de::Error::custom(format_args!("{}: expected at most {} arguments", ErrorCode::InvalidParams.message(), self.len)))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn next_arg<T: DeserializeSeed<'a>>(&mut self, seed: T) -> Result<T::Value, S::Error> {
+        let name = self.fields.get(self.idx).copied();
+        self.idx += 1;
+
+        let mut seed = Some(seed);
+        if !self.is_done {
+            let arg = ArgSeed {
+                seed: &mut seed,
+                idx: self.idx,
+                name,
+            };
+
+            match self.seq.next_element_seed(arg)? {
+                Some(value) => return Ok(value),
+                None => self.is_done = true,
+            }
+        }
+
+        match seed {
+            Some(seed) => seed.deserialize(MissingArg {
+                idx: self.idx,
+                name,
+                _err: PhantomData,
+            }),
+            None => Err(de::Error::custom("Argument is requested twice")),
+        }
+    }
+}
+
+impl<'a, S: SeqAccess<'a>> SeqAccess<'a> for PositionalArgs<S> {
+    type Error = S::Error;
+
+    #[inline]
+    fn next_element_seed<T: DeserializeSeed<'a>>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> {
+        if self.idx < self.len {
+            self.next_arg(seed).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.idx)
+    }
+}
+
+impl<'a, S: SeqAccess<'a>> MapAccess<'a> for PositionalArgs<S> {
+    type Error = S::Error;
+
+    #[inline]
+    fn next_key_seed<K: DeserializeSeed<'a>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        if self.is_done {
+            return Ok(None);
+        }
+
+        match self.fields.get(self.idx) {
+            Some(name) => seed.deserialize(BorrowedStrDeserializer::new(name)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    #[inline(always)]
+    fn next_value_seed<V: DeserializeSeed<'a>>(&mut self, seed: V) -> Result<V::Value, Self::Error> {
+        self.next_arg(seed)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len - self.idx)
+    }
+}
+
+///Positional argument, that reports its position on failure.
+struct ArgSeed<'b, T> {
+    seed: &'b mut Option<T>,
+    idx: usize,
+    name: Option<&'static str>,
+}
+
+impl<'a, 'b, T: DeserializeSeed<'a>> DeserializeSeed<'a> for ArgSeed<'b, T> {
+    type Value = T::Value;
+
+    fn deserialize<D: Deserializer<'a>>(self, des: D) -> Result<Self::Value, D::Error> {
+        let seed = match self.seed.take() {
+            Some(seed) => seed,
+            None => return Err(de::Error::custom("Argument is requested twice")),
+        };
+
+        match (seed.deserialize(des), self.name) {
+            (Ok(value), _) => Ok(value),
+            (Err(error), Some(name)) => Err(de::Error::custom(format_args!("Invalid argument #{} '{}': {}", self.idx, name, error))),
+            (Err(error), None) => Err(de::Error::custom(format_args!("Invalid argument #{}: {}", self.idx, error))),
+        }
+    }
+}
+
+///Omitted trailing argument, that can only be deserialized as `None`.
+struct MissingArg<E> {
+    idx: usize,
+    name: Option<&'static str>,
+    _err: PhantomData<E>,
+}
+
+impl<'de, E: de::Error> Deserializer<'de> for MissingArg<E> {
+    type Error = E;
+
+    #[inline]
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        match self.name {
+            Some(name) => Err(de::Error::custom(format_args!("Missing argument #{} '{}'", self.idx, name))),
+            None => Err(de::Error::custom(format_args!("Missing argument #{}", self.idx))),
+        }
+    }
+
+    #[inline]
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_none()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
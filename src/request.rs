@@ -1,8 +1,12 @@
-use serde_derive::{Serialize, Deserialize};
-type StrBuf = str_buf::StrBuf<32>;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde::ser::SerializeMap;
+pub(crate) type StrBuf = str_buf::StrBuf<32>;
 
 use crate::version::Version;
 use crate::id::Id;
+use crate::error::{Error, ErrorCode, BuildError};
+use crate::response::Response;
+use crate::text::FromText;
 
 ///Request representation.
 ///
@@ -10,24 +14,31 @@ use crate::id::Id;
 ///response.
 ///This can be used to indicate lack of interest in response.
 ///
+///When `jsonrpc` is `V1`, request is serialized according to JSON-RPCv1:
+///
+///- `jsonrpc` is omitted;
+///- `params` is always present, defaulting to empty array;
+///- `id` is always present and is `null` for notification.
+///
+///When `jsonrpc` is `V1_1`, it is serialized as `version` member.
+///
+///During deserialization, request without `jsonrpc` is treated as `V2`, unless wrapped in `Legacy`,
+///in which case request with `id` is treated as `V1`.
+///
 ///Type parameters:
 ///
 ///- `P` - to specify type of `params` field, which is optional. Normally it should be collection of values or object. But choice is yours.
 ///- `T` - specifies textual type. By default it uses static buffer of 32 bytes, which is more than enough in normal cases.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Request<P, T=StrBuf> {
     ///A String specifying the version of the JSON-RPC protocol.
-    #[serde(default)]
     pub jsonrpc: Version,
     ///A String containing the name of the method to be invoked
     ///
     ///By default is static buffer of 32 bytes.
     pub method: T,
-    #[serde(skip_serializing_if = "Option::is_none")]
     ///A Structured value that holds the parameter values to be used during the invocation of the method
     pub params: Option<P>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     ///An identifier established by the Client.
     ///
     ///If not present, request is notification to which
@@ -36,8 +47,279 @@ pub struct Request<P, T=StrBuf> {
 }
 
 impl<P, T> Request<P, T> {
+    #[inline]
+    ///Creates JSON-RPCv2 notification without params.
+    ///
+    ///Use `set_*` methods to fill the rest.
+    pub const fn new(method: T) -> Self {
+        Self {
+            jsonrpc: Version::V2,
+            method,
+            params: None,
+            id: None,
+        }
+    }
+
+    #[inline]
+    ///Creates JSON-RPCv2 notification without params, converting method from string.
+    ///
+    ///Fails if `method` cannot be converted into `T`, e.g. due to insufficient capacity.
+    pub fn try_new<'a>(method: &'a str) -> Result<Self, BuildError> where T: FromText<'a> {
+        match T::from_text(method) {
+            Some(method) => Ok(Self::new(method)),
+            None => Err(BuildError::Method {
+                len: method.len(),
+            }),
+        }
+    }
+
+    #[inline(always)]
+    ///Sets protocol version.
+    pub fn set_version(mut self, jsonrpc: Version) -> Self {
+        self.jsonrpc = jsonrpc;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets params.
+    pub fn set_params(mut self, params: P) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    #[inline(always)]
+    ///Sets id, making request a call, rather than notification.
+    pub fn set_id<I: Into<Id>>(mut self, id: I) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    #[inline]
+    ///Sets id, converting it from text or other type, making request a call, rather than notification.
+    ///
+    ///Fails if `id` cannot be converted, e.g. string exceeding capacity of `Id`.
+    pub fn try_set_id<I: core::convert::TryInto<Id>>(self, id: I) -> Result<Self, BuildError> {
+        match id.try_into() {
+            Ok(id) => Ok(self.set_id(id)),
+            Err(_) => Err(BuildError::Id),
+        }
+    }
+
     ///Returns whether request is notification.
     pub const fn is_notification(&self) -> bool {
         self.id.is_none()
     }
+
+    #[inline]
+    ///Creates successful response to this request, using its version and id.
+    ///
+    ///Returns `None` for notification.
+    pub fn reply_ok<R, E, EM>(&self, result: R) -> Option<Response<R, E, EM>> {
+        self.id.as_ref().map(|id| Response::result(self.jsonrpc, result, Some(id.clone())))
+    }
+
+    #[inline]
+    ///Creates error response to this request, using its version and id.
+    ///
+    ///Returns `None` for notification.
+    pub fn reply_err<R, E, EM>(&self, error: Error<E, EM>) -> Option<Response<R, E, EM>> {
+        self.id.as_ref().map(|id| Response::error(self.jsonrpc, error, Some(id.clone())))
+    }
+
+    #[inline]
+    ///Creates error response to this request with message derived from `code`, using its version and id.
+    ///
+    ///Returns `None` for notification.
+    pub fn reply_with<R, E>(&self, code: ErrorCode) -> Option<Response<R, E>> {
+        self.reply_err(Error::from_code(code))
+    }
+}
+
+impl<P: Serialize, T: Serialize> Request<P, T> {
+    pub(crate) fn serialize_members<M: SerializeMap>(&self, state: &mut M) -> Result<(), M::Error> {
+        match self.jsonrpc {
+            Version::V1 => {
+                state.serialize_entry("method", &self.method)?;
+                match self.params {
+                    Some(ref params) => state.serialize_entry("params", params)?,
+                    None => state.serialize_entry("params", &[(); 0])?,
+                }
+                match self.id {
+                    Some(ref id) => state.serialize_entry("id", id),
+                    None => state.serialize_entry("id", &()),
+                }
+            },
+            Version::V1_1 | Version::V2 => {
+                match self.jsonrpc {
+                    Version::V1_1 => state.serialize_entry("version", &self.jsonrpc)?,
+                    _ => state.serialize_entry("jsonrpc", &self.jsonrpc)?,
+                }
+                state.serialize_entry("method", &self.method)?;
+                if let Some(ref params) = self.params {
+                    state.serialize_entry("params", params)?;
+                }
+                if let Some(ref id) = self.id {
+                    state.serialize_entry("id", id)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<P: Serialize, T: Serialize> Serialize for Request<P, T> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let len = match self.jsonrpc {
+            Version::V1 => 3,
+            Version::V1_1 | Version::V2 => 2 + self.params.is_some() as usize + self.id.is_some() as usize,
+        };
+        let mut state = ser.serialize_map(Some(len))?;
+        self.serialize_members(&mut state)?;
+        state.end()
+    }
+}
+
+///Visitor of request, which infers `V1` from request's shape when `INFER_V1` is set.
+pub(crate) struct RequestVisitor<P, T, const INFER_V1: bool>(pub(crate) core::marker::PhantomData<(P, T)>);
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>, const INFER_V1: bool> de::Visitor<'de> for RequestVisitor<P, T, INFER_V1> {
+    type Value = Request<P, T>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("Object resembling JSON-RPC request type")
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut members = Members::new();
+        if INFER_V1 {
+            members.legacy();
+        }
+        crate::ext::visit_extensions::<_, _, ()>(&mut map, &mut members)?;
+        members.finish()
+    }
+}
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>> Deserialize<'de> for Request<P, T> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        der.deserialize_map(RequestVisitor::<P, T, false>(core::marker::PhantomData))
+    }
+}
+
+pub(crate) enum Key {
+    JsonRpc,
+    Version,
+    Method,
+    Params,
+    Id,
+}
+
+pub(crate) struct Members<P, T> {
+    version: Option<Version>,
+    method: Option<T>,
+    params: Option<P>,
+    id: Option<Id>,
+    has_params: bool,
+    has_id: bool,
+    legacy: bool,
+}
+
+impl<P, T> Members<P, T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            version: None,
+            method: None,
+            params: None,
+            id: None,
+            has_params: false,
+            has_id: false,
+            legacy: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Enables `version` member and inference of `V1` from presence of `id`, when version is not specified.
+    pub(crate) fn legacy(&mut self) {
+        self.legacy = true;
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_version(&mut self, version: Version) {
+        self.version = Some(version);
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_id(&mut self, id: Option<Id>) {
+        self.has_id = true;
+        self.id = id;
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.method.is_none() && self.params.is_none()
+    }
+
+    pub(crate) fn finish<E: de::Error>(self) -> Result<Request<P, T>, E> {
+        Ok(Request {
+            jsonrpc: match self.version {
+                Some(version) => version,
+                //Only JSON-RPCv1 requires `id` to be present always.
+                None if self.legacy && self.has_id => Version::V1,
+                None => Version::V2,
+            },
+            method: match self.method {
+                Some(method) => method,
+                None => return Err(E::missing_field("method")),
+            },
+            params: self.params,
+            id: self.id,
+        })
+    }
+}
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>> crate::ext::Members<'de> for Members<P, T> {
+    type Key = Key;
+    const NAMES: &'static [&'static str] = &["jsonrpc", "version", "method", "params", "id"];
+
+    #[inline]
+    fn key(text: &str) -> Option<Self::Key> {
+        match text {
+            "jsonrpc" => Some(Key::JsonRpc),
+            "version" => Some(Key::Version),
+            "method" => Some(Key::Method),
+            "params" => Some(Key::Params),
+            "id" => Some(Key::Id),
+            _ => None,
+        }
+    }
+
+    fn visit_value<A: de::MapAccess<'de>>(&mut self, key: Self::Key, map: &mut A) -> Result<(), A::Error> {
+        match key {
+            Key::JsonRpc if self.version.is_some() => Err(de::Error::duplicate_field("jsonrpc")),
+            Key::Version if !self.legacy => Err(crate::utils::legacy_version()),
+            Key::Version if self.version.is_some() => Err(de::Error::duplicate_field("version")),
+            Key::JsonRpc | Key::Version => {
+                self.version = Some(map.next_value()?);
+                Ok(())
+            },
+            Key::Method if self.method.is_some() => Err(de::Error::duplicate_field("method")),
+            Key::Method => {
+                self.method = Some(map.next_value()?);
+                Ok(())
+            },
+            Key::Params if self.has_params => Err(de::Error::duplicate_field("params")),
+            Key::Params => {
+                self.has_params = true;
+                self.params = map.next_value()?;
+                Ok(())
+            },
+            Key::Id if self.has_id => Err(de::Error::duplicate_field("id")),
+            Key::Id => {
+                self.has_id = true;
+                self.id = map.next_value()?;
+                Ok(())
+            },
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de;
+use serde::ser::SerializeMap;
 
-use crate::error::Error;
+use crate::error::{Error, ErrorCode, ClientError, BuildError};
 use crate::version::Version;
 use crate::id::Id;
 use crate::utils::Key;
@@ -16,6 +18,11 @@ use core::mem;
 ///indication that server treats request as invalid (e.g. unable to parse request's id).
 ///
 ///`jsonrpc` may be omitted during deserialization and defaults to v2.
+///When wrapped in `Legacy`, response without `jsonrpc`, that contains both `result` and `error`, is treated as v1.
+///
+///When `jsonrpc` is `V1`, response is serialized without `jsonrpc` member, but with both `result`
+///and `error`, one of which is `null`.
+///When `jsonrpc` is `V1_1`, it is serialized as `version` member.
 ///
 ///Type parameters:
 ///
@@ -38,103 +45,210 @@ pub struct Response<R, E, EM=crate::error::StrBuf> {
     pub id: Option<Id>,
 }
 
-impl<R: Serialize, E: Serialize, EM: Serialize> Serialize for Response<R, E, EM> {
-    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
-        use serde::ser::SerializeMap;
-
-        let mut state = ser.serialize_map(Some(3))?;
-
-        state.serialize_entry("jsonrpc", &self.jsonrpc)?;
-        match self.payload {
-            Ok(ref result) => state.serialize_entry("result", result),
-            Err(ref error) => state.serialize_entry("error", error),
+impl<R: Serialize, E: Serialize, EM: Serialize> Response<R, E, EM> {
+    pub(crate) fn serialize_members<M: SerializeMap>(&self, state: &mut M) -> Result<(), M::Error> {
+        match self.jsonrpc {
+            Version::V1 => match self.payload {
+                Ok(ref result) => {
+                    state.serialize_entry("result", result)?;
+                    state.serialize_entry("error", &())
+                },
+                Err(ref error) => {
+                    state.serialize_entry("result", &())?;
+                    state.serialize_entry("error", error)
+                },
+            },
+            Version::V1_1 | Version::V2 => {
+                match self.jsonrpc {
+                    Version::V1_1 => state.serialize_entry("version", &self.jsonrpc)?,
+                    _ => state.serialize_entry("jsonrpc", &self.jsonrpc)?,
+                }
+                match self.payload {
+                    Ok(ref result) => state.serialize_entry("result", result),
+                    Err(ref error) => state.serialize_entry("error", error),
+                }
+            },
         }?;
         match self.id {
             Some(ref id) => state.serialize_entry("id", id),
             None => state.serialize_entry("id", &()),
-        }?;
+        }
+    }
+}
 
+impl<R: Serialize, E: Serialize, EM: Serialize> Serialize for Response<R, E, EM> {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut state = ser.serialize_map(Some(3))?;
+        self.serialize_members(&mut state)?;
         state.end()
     }
 }
 
-impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> Deserialize<'de> for Response<R, E, EM> {
-    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
-        use core::marker::PhantomData;
-        use serde::de::{self, Visitor};
+pub(crate) struct Members<R, E, EM> {
+    version: Option<Version>,
+    result: Option<Result<R, Error<E, EM>>>,
+    id: Option<Id>,
+    has_result: bool,
+    has_error: bool,
+    is_conflict: bool,
+    legacy: bool,
+}
 
-        struct MapVisit<R, E, EM>(PhantomData<(R, E, EM)>);
+impl<R, E, EM> Members<R, E, EM> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            version: None,
+            result: None,
+            id: None,
+            has_result: false,
+            has_error: false,
+            is_conflict: false,
+            legacy: false,
+        }
+    }
 
-        impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> Visitor<'de> for MapVisit<R, E, EM> {
-            type Value = Response<R, E, EM>;
+    #[inline(always)]
+    ///Enables `version` member and inference of `V1` from presence of both `result` and `error`, when version is not specified.
+    pub(crate) fn legacy(&mut self) {
+        self.legacy = true;
+    }
 
-            #[inline]
-            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
-                formatter.write_str("Object resembling JSON-RPC response type")
-            }
+    #[inline(always)]
+    pub(crate) fn set_version(&mut self, version: Version) {
+        self.version = Some(version);
+    }
 
-            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
-                //Normally you'd use unitialized struct, but it is highly unlikely to guarantee
-                //safety of field-by-field initialization
-                let mut version = None;
-                let mut result = None;
-                let mut id = None;
-
-                while let Some(key) = map.next_key::<Key>()? {
-                    match key {
-                        Key::JsonRpc => {
-                            version = Some(map.next_value::<Version>()?);
-                        },
-                        //If for some reason user wishes to convey success with NULL, we need to respect that.
-                        //This cannot be the case for error as its format is well defined
-                        //And while spec does say `result` field MUST be object, theoretically NULL should qualify too.
-                        //This is hack because bitch cannot have specialization stabilized forever
-                        Key::Result if mem::size_of::<R>() == 0 => {
-                            if result.is_none() {
-                                result = Some(Ok(map.next_value::<R>()?));
-                            } else {
-                                return Err(serde::de::Error::custom("JSON-RPC Response contains both result and error field"));
-                            }
-                        }
-                        Key::Result => match map.next_value::<Option<R>>()? {
-                            Some(value) => if result.is_none() {
-                                result = Some(Ok(value));
-                            } else {
-                                return Err(serde::de::Error::custom("JSON-RPC Response contains both result and error field"));
-                            }
-                            None => continue,
-                        },
-                        Key::Error => match map.next_value::<Option<Error<E, EM>>>()? {
-                            Some(error) => if result.is_none() {
-                                result = Some(Err(error));
-                            } else {
-                                return Err(serde::de::Error::custom("JSON-RPC Response contains both error and result field"));
-                            }
-                            None => continue,
-                        },
-                        Key::Id => {
-                            id = map.next_value::<Option<Id>>()?;
+    #[inline(always)]
+    pub(crate) fn set_id(&mut self, id: Option<Id>) {
+        self.id = id;
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        !self.has_result && !self.has_error
+    }
+
+    pub(crate) fn finish<ERR: de::Error>(self) -> Result<Response<R, E, EM>, ERR> {
+        if self.is_conflict && (self.version.is_some() || !self.legacy) {
+            return Err(de::Error::custom("JSON-RPC Response contains both result and error field"));
+        }
+
+        Ok(Response {
+            jsonrpc: match self.version {
+                Some(version) => version,
+                //Only JSON-RPCv1 requires both `result` and `error` to be present.
+                None if self.legacy && self.has_result && self.has_error => Version::V1,
+                None => Version::V2,
+            },
+            payload: match self.result {
+                Some(payload) => payload,
+                None => {
+                    return Err(de::Error::custom("JSON-RPC Response is missing either result or error field."));
+                }
+            },
+            id: self.id,
+        })
+    }
+}
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> crate::ext::Members<'de> for Members<R, E, EM> {
+    type Key = Key;
+    const NAMES: &'static [&'static str] = Key::NAMES;
+
+    #[inline(always)]
+    fn key(text: &str) -> Option<Self::Key> {
+        Key::from_str(text)
+    }
+
+    fn visit_value<A: de::MapAccess<'de>>(&mut self, key: Self::Key, map: &mut A) -> Result<(), A::Error> {
+        match key {
+            Key::Version if !self.legacy => return Err(crate::utils::legacy_version()),
+            Key::JsonRpc | Key::Version => {
+                self.version = Some(map.next_value::<Version>()?);
+            },
+            //If for some reason user wishes to convey success with NULL, we need to respect that.
+            //This cannot be the case for error as its format is well defined
+            //And while spec does say `result` field MUST be object, theoretically NULL should qualify too.
+            //This is hack because bitch cannot have specialization stabilized forever
+            //
+            //JSON-RPCv1 sends NULL `result` alongside with `error` though, so within `Legacy` conflict
+            //is resolved in favour of error, once it is known that `jsonrpc` is absent.
+            Key::Result if self.has_result => return Err(de::Error::duplicate_field("result")),
+            Key::Error if self.has_error => return Err(de::Error::duplicate_field("error")),
+            Key::Result if mem::size_of::<R>() == 0 => {
+                self.has_result = true;
+                let result = map.next_value::<R>()?;
+                match self.result {
+                    None => self.result = Some(Ok(result)),
+                    Some(Err(_)) => self.is_conflict = true,
+                    Some(Ok(_)) => return Err(serde::de::Error::custom("JSON-RPC Response contains both result and error field")),
+                }
+            }
+            Key::Result => {
+                self.has_result = true;
+                if let Some(value) = map.next_value::<Option<R>>()? {
+                    if self.result.is_none() {
+                        self.result = Some(Ok(value));
+                    } else {
+                        return Err(serde::de::Error::custom("JSON-RPC Response contains both result and error field"));
+                    }
+                }
+            },
+            Key::Error => {
+                self.has_error = true;
+                if let Some(error) = map.next_value::<Option<Error<E, EM>>>()? {
+                    match self.result {
+                        None => self.result = Some(Err(error)),
+                        Some(Ok(_)) if mem::size_of::<R>() == 0 => {
+                            self.is_conflict = true;
+                            self.result = Some(Err(error));
                         },
+                        Some(_) => return Err(serde::de::Error::custom("JSON-RPC Response contains both error and result field")),
                     }
                 }
+            },
+            Key::Id => {
+                self.id = map.next_value::<Option<Id>>()?;
+            },
+        }
 
-                Ok(Self::Value {
-                    jsonrpc: match version {
-                        Some(version) => version,
-                        None => Version::V2,
-                    },
-                    payload: match result {
-                        Some(payload) => payload,
-                        None => {
-                            return Err(serde::de::Error::custom("JSON-RPC Response is missing either result or error field."));
-                        }
-                    },
-                    id,
-                })
-            }
+        Ok(())
+    }
+}
+
+///Visitor of response, which infers `V1` from response's shape when `INFER_V1` is set.
+pub(crate) struct ResponseVisitor<R, E, EM, const INFER_V1: bool>(pub(crate) core::marker::PhantomData<(R, E, EM)>);
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>, const INFER_V1: bool> de::Visitor<'de> for ResponseVisitor<R, E, EM, INFER_V1> {
+    type Value = Response<R, E, EM>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+        formatter.write_str("Object resembling JSON-RPC response type")
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        use crate::ext::Members as _;
+
+        //Normally you'd use unitialized struct, but it is highly unlikely to guarantee
+        //safety of field-by-field initialization
+        let mut members = Members::new();
+        if INFER_V1 {
+            members.legacy();
         }
 
-        der.deserialize_map(MapVisit(PhantomData))
+        while let Some(key) = map.next_key::<Key>()? {
+            members.visit_value(key, &mut map)?;
+        }
+
+        members.finish()
+    }
+}
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> Deserialize<'de> for Response<R, E, EM> {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        der.deserialize_map(ResponseVisitor::<R, E, EM, false>(core::marker::PhantomData))
     }
 }
 
@@ -159,3 +273,104 @@ impl<R, E, EM> Response<R, E, EM> {
         }
     }
 }
+
+impl<R, E, EM> Response<R, E, EM> {
+    #[inline]
+    ///Creates successful JSON-RPCv2 response without id.
+    ///
+    ///Use `set_*` methods to fill the rest.
+    pub const fn ok(result: R) -> Self {
+        Self::result(Version::V2, result, None)
+    }
+
+    #[inline]
+    ///Creates error JSON-RPCv2 response without id.
+    ///
+    ///Use `set_*` methods to fill the rest.
+    pub const fn err(error: Error<E, EM>) -> Self {
+        Self::error(Version::V2, error, None)
+    }
+
+    #[inline(always)]
+    ///Sets protocol version.
+    pub fn set_version(mut self, jsonrpc: Version) -> Self {
+        self.jsonrpc = jsonrpc;
+        self
+    }
+
+    #[inline(always)]
+    ///Sets id.
+    pub fn set_id<I: Into<Id>>(mut self, id: I) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    #[inline]
+    ///Sets id, converting it from text or other type.
+    ///
+    ///Fails if `id` cannot be converted, e.g. string exceeding capacity of `Id`.
+    pub fn try_set_id<I: core::convert::TryInto<Id>>(self, id: I) -> Result<Self, BuildError> {
+        match id.try_into() {
+            Ok(id) => Ok(self.set_id(id)),
+            Err(_) => Err(BuildError::Id),
+        }
+    }
+
+    #[inline]
+    ///Returns whether response is successful.
+    pub const fn is_success(&self) -> bool {
+        self.payload.is_ok()
+    }
+
+    #[inline]
+    ///Returns reference to payload.
+    pub const fn as_result(&self) -> Result<&R, &Error<E, EM>> {
+        self.payload.as_ref()
+    }
+
+    #[inline]
+    ///Returns payload.
+    pub fn into_result(self) -> Result<R, Error<E, EM>> {
+        self.payload
+    }
+
+    #[inline]
+    ///Maps successful result, leaving error untouched.
+    pub fn map_result<O, F: FnOnce(R) -> O>(self, cb: F) -> Response<O, E, EM> {
+        Response {
+            jsonrpc: self.jsonrpc,
+            payload: self.payload.map(cb),
+            id: self.id,
+        }
+    }
+
+    #[inline]
+    ///Maps error's data, leaving successful result untouched.
+    pub fn map_error_data<O, F: FnOnce(E) -> O>(self, cb: F) -> Response<R, O, EM> {
+        Response {
+            jsonrpc: self.jsonrpc,
+            payload: self.payload.map_err(|error| Error {
+                code: error.code,
+                message: error.message,
+                data: error.data.map(cb),
+            }),
+            id: self.id,
+        }
+    }
+
+    ///Returns result of request with specified `id`, folding any failure into `ClientError`.
+    ///
+    ///Error with code `ParseError` or `InvalidRequest` is treated as protocol error, regardless of
+    ///`id`, as server might be unable to determine it.
+    pub fn into_client_result(self, id: &Id) -> Result<R, ClientError<E, EM>> {
+        match self.payload {
+            Err(error) if matches!(error.code, ErrorCode::ParseError | ErrorCode::InvalidRequest) => Err(ClientError::Protocol(error)),
+            _ if self.id.as_ref() != Some(id) => Err(ClientError::IdMismatch {
+                expected: id.clone(),
+                actual: self.id,
+            }),
+            Ok(result) => Ok(result),
+            Err(error) => Err(ClientError::Rpc(error)),
+        }
+    }
+}
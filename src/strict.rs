@@ -0,0 +1,237 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, Error as _, MapAccess, Visitor};
+
+use crate::error::Error;
+use crate::version::Version;
+use crate::id::Id;
+use crate::request::Request;
+use crate::response::Response;
+use crate::utils::Structured;
+
+use core::fmt;
+use core::marker::PhantomData;
+
+const REQUEST_FIELDS: &[&str] = &["jsonrpc", "method", "params", "id"];
+const RESPONSE_FIELDS: &[&str] = &["jsonrpc", "result", "error", "id"];
+
+///Wrapper to enforce strict conformance to JSON-RPCv2 specification during deserialization.
+///
+///On top of regular rules, following is required:
+///
+///- Member names must match exactly (i.e. be lowercase);
+///- `jsonrpc` member must be present and be `"2.0"`;
+///- `params` of `Request` must be either array or object;
+///- `id` member of `Response` must be present, although it can be `null`;
+///- `Response` must contain exactly one of `result` or `error` members, `null` is not treated as absence.
+///
+///Serialization is the same as for inner type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Strict<T>(pub T);
+
+impl_wrapper!(Strict);
+
+impl<T: Serialize> Serialize for Strict<T> {
+    #[inline(always)]
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(ser)
+    }
+}
+
+#[inline]
+fn expect_v2<E: de::Error>(version: Version) -> Result<Version, E> {
+    match version {
+        Version::V2 => Ok(version),
+        version => Err(E::invalid_value(de::Unexpected::Str(version.as_str()), &"2.0")),
+    }
+}
+
+enum RequestKey {
+    JsonRpc,
+    Method,
+    Params,
+    Id,
+}
+
+impl<'a> Deserialize<'a> for RequestKey {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'a> Visitor<'a> for KeyVisitor {
+            type Value = RequestKey;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Key must be a string and one of the following values: ['jsonrpc', 'method', 'params', 'id']")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+                match text {
+                    "jsonrpc" => Ok(RequestKey::JsonRpc),
+                    "method" => Ok(RequestKey::Method),
+                    "params" => Ok(RequestKey::Params),
+                    "id" => Ok(RequestKey::Id),
+                    _ => Err(de::Error::unknown_field(text, REQUEST_FIELDS)),
+                }
+            }
+        }
+
+        des.deserialize_identifier(KeyVisitor)
+    }
+}
+
+enum ResponseKey {
+    JsonRpc,
+    Result,
+    Error,
+    Id,
+}
+
+impl<'a> Deserialize<'a> for ResponseKey {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'a> Visitor<'a> for KeyVisitor {
+            type Value = ResponseKey;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Key must be a string and one of the following values: ['jsonrpc', 'result', 'error', 'id']")
+            }
+
+            #[inline]
+            fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+                match text {
+                    "jsonrpc" => Ok(ResponseKey::JsonRpc),
+                    "result" => Ok(ResponseKey::Result),
+                    "error" => Ok(ResponseKey::Error),
+                    "id" => Ok(ResponseKey::Id),
+                    _ => Err(de::Error::unknown_field(text, RESPONSE_FIELDS)),
+                }
+            }
+        }
+
+        des.deserialize_identifier(KeyVisitor)
+    }
+}
+
+impl<'de, P: Deserialize<'de>, T: Deserialize<'de>> Deserialize<'de> for Strict<Request<P, T>> {
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct MapVisit<P, T>(PhantomData<(P, T)>);
+
+        impl<'de, P: Deserialize<'de>, T: Deserialize<'de>> Visitor<'de> for MapVisit<P, T> {
+            type Value = Strict<Request<P, T>>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Object resembling JSON-RPCv2 request type")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut version = None;
+                let mut method = None;
+                let mut params = None;
+                let mut id = None;
+
+                while let Some(key) = map.next_key::<RequestKey>()? {
+                    match key {
+                        RequestKey::JsonRpc => match version {
+                            Some(_) => return Err(A::Error::duplicate_field("jsonrpc")),
+                            None => version = Some(expect_v2(map.next_value::<Version>()?)?),
+                        },
+                        RequestKey::Method => match method {
+                            Some(_) => return Err(A::Error::duplicate_field("method")),
+                            None => method = Some(map.next_value::<T>()?),
+                        },
+                        RequestKey::Params => match params {
+                            Some(_) => return Err(A::Error::duplicate_field("params")),
+                            None => params = Some(map.next_value::<Structured<P>>()?.0),
+                        },
+                        RequestKey::Id => match id {
+                            Some(_) => return Err(A::Error::duplicate_field("id")),
+                            None => id = Some(map.next_value::<Option<Id>>()?),
+                        },
+                    }
+                }
+
+                Ok(Strict(Request {
+                    jsonrpc: match version {
+                        Some(version) => version,
+                        None => return Err(A::Error::missing_field("jsonrpc")),
+                    },
+                    method: match method {
+                        Some(method) => method,
+                        None => return Err(A::Error::missing_field("method")),
+                    },
+                    params,
+                    id: id.unwrap_or(None),
+                }))
+            }
+        }
+
+        der.deserialize_struct("Request", REQUEST_FIELDS, MapVisit(PhantomData))
+    }
+}
+
+impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> Deserialize<'de> for Strict<Response<R, E, EM>> {
+    fn deserialize<D: Deserializer<'de>>(der: D) -> Result<Self, D::Error> {
+        struct MapVisit<R, E, EM>(PhantomData<(R, E, EM)>);
+
+        impl<'de, R: Deserialize<'de>, E: Deserialize<'de>, EM: Deserialize<'de>> Visitor<'de> for MapVisit<R, E, EM> {
+            type Value = Strict<Response<R, E, EM>>;
+
+            #[inline]
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("Object resembling JSON-RPCv2 response type")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut version = None;
+                let mut result = None;
+                let mut id = None;
+
+                while let Some(key) = map.next_key::<ResponseKey>()? {
+                    match key {
+                        ResponseKey::JsonRpc => match version {
+                            Some(_) => return Err(A::Error::duplicate_field("jsonrpc")),
+                            None => version = Some(expect_v2(map.next_value::<Version>()?)?),
+                        },
+                        ResponseKey::Result => match result {
+                            Some(Ok(_)) => return Err(A::Error::duplicate_field("result")),
+                            Some(Err(_)) => return Err(A::Error::custom("JSON-RPC Response contains both error and result field")),
+                            None => result = Some(Ok(map.next_value::<R>()?)),
+                        },
+                        ResponseKey::Error => match result {
+                            Some(Err(_)) => return Err(A::Error::duplicate_field("error")),
+                            Some(Ok(_)) => return Err(A::Error::custom("JSON-RPC Response contains both result and error field")),
+                            None => result = Some(Err(map.next_value::<Error<E, EM>>()?)),
+                        },
+                        ResponseKey::Id => match id {
+                            Some(_) => return Err(A::Error::duplicate_field("id")),
+                            None => id = Some(map.next_value::<Option<Id>>()?),
+                        },
+                    }
+                }
+
+                Ok(Strict(Response {
+                    jsonrpc: match version {
+                        Some(version) => version,
+                        None => return Err(A::Error::missing_field("jsonrpc")),
+                    },
+                    payload: match result {
+                        Some(payload) => payload,
+                        None => return Err(A::Error::custom("JSON-RPC Response is missing either result or error field.")),
+                    },
+                    id: match id {
+                        Some(id) => id,
+                        None => return Err(A::Error::missing_field("id")),
+                    },
+                }))
+            }
+        }
+
+        der.deserialize_struct("Response", RESPONSE_FIELDS, MapVisit(PhantomData))
+    }
+}
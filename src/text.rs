@@ -0,0 +1,33 @@
+///Text type of request's `method` and error's `message`, which can be built out of string.
+///
+///Unlike `TryFrom<&str>`, conversion never panics, failing instead when text doesn't fit capacity.
+pub trait FromText<'a>: Sized {
+    ///Converts `text`, returning `None` if it cannot be stored.
+    fn from_text(text: &'a str) -> Option<Self>;
+}
+
+impl<'a> FromText<'a> for &'a str {
+    #[inline(always)]
+    fn from_text(text: &'a str) -> Option<Self> {
+        Some(text)
+    }
+}
+
+impl<'a, const N: usize> FromText<'a> for str_buf::StrBuf<N> {
+    #[inline(always)]
+    fn from_text(text: &'a str) -> Option<Self> {
+        Self::from_str_checked(text).ok()
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<'a, const N: usize> FromText<'a> for heapless::String<N> {
+    #[inline]
+    fn from_text(text: &'a str) -> Option<Self> {
+        let mut result = Self::new();
+        match result.push_str(text) {
+            Ok(()) => Some(result),
+            Err(()) => None,
+        }
+    }
+}
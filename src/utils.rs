@@ -1,15 +1,116 @@
+///Implements common methods and traits of wrapper, which holds value as first field.
+///
+///Wrapper with policy `P` holds `PhantomData<P>` as second field, so traits are implemented regardless of `P`.
+macro_rules! impl_wrapper {
+    ($name:ident) => {
+        impl<T> $name<T> {
+            #[inline(always)]
+            ///Returns inner value.
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl_wrapper!(@deref $name<T>);
+    };
+    ($name:ident<$policy:ident>) => {
+        impl<T, $policy> $name<T, $policy> {
+            #[inline(always)]
+            ///Creates new instance
+            pub const fn new(value: T) -> Self {
+                Self(value, core::marker::PhantomData)
+            }
+
+            #[inline(always)]
+            ///Returns inner value.
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T: Clone, $policy> Clone for $name<T, $policy> {
+            #[inline(always)]
+            fn clone(&self) -> Self {
+                Self::new(self.0.clone())
+            }
+        }
+
+        impl<T: core::fmt::Debug, $policy> core::fmt::Debug for $name<T, $policy> {
+            #[inline(always)]
+            fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.0, fmt)
+            }
+        }
+
+        impl<T: PartialEq, $policy> PartialEq for $name<T, $policy> {
+            #[inline(always)]
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl_wrapper!(@deref $name<T, $policy>);
+    };
+    (@deref $name:ident<$($param:ident),+>) => {
+        impl<$($param),+> core::ops::Deref for $name<$($param),+> {
+            type Target = T;
+
+            #[inline(always)]
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<$($param),+> core::ops::DerefMut for $name<$($param),+> {
+            #[inline(always)]
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    };
+}
+
 use serde::{Deserialize, Deserializer};
-use serde::de::{Error, Visitor};
+use serde::de::{Error, Visitor, SeqAccess, MapAccess};
+use serde::de::value::{SeqAccessDeserializer, MapAccessDeserializer};
 
 use core::fmt;
+use core::marker::PhantomData;
 
 pub enum Key {
     JsonRpc,
+    Version,
     Result,
     Error,
     Id,
 }
 
+impl Key {
+    pub const NAMES: &'static [&'static str] = &["jsonrpc", "version", "result", "error", "id"];
+
+    pub fn from_str(text: &str) -> Option<Self> {
+        if text.eq_ignore_ascii_case("jsonrpc") {
+            Some(Key::JsonRpc)
+        } else if text.eq_ignore_ascii_case("version") {
+            Some(Key::Version)
+        } else if text.eq_ignore_ascii_case("result") {
+            Some(Key::Result)
+        } else if text.eq_ignore_ascii_case("error") {
+            Some(Key::Error)
+        } else if text.eq_ignore_ascii_case("id") {
+            Some(Key::Id)
+        } else {
+            None
+        }
+    }
+}
+
+///Error on `version` member of JSON-RPCv1.1, which is accepted only within `Legacy`.
+#[inline]
+pub fn legacy_version<E: Error>() -> E {
+    E::custom("JSON-RPCv1.1 `version` member is accepted only within Legacy")
+}
+
 struct KeyVisitor;
 
 impl<'a> Visitor<'a> for KeyVisitor {
@@ -22,16 +123,9 @@ impl<'a> Visitor<'a> for KeyVisitor {
 
     #[inline]
     fn visit_str<E: Error>(self, text: &str) -> Result<Self::Value, E> {
-        if text.eq_ignore_ascii_case("jsonrpc") {
-            Ok(Key::JsonRpc)
-        } else if text.eq_ignore_ascii_case("result") {
-            Ok(Key::Result)
-        } else if text.eq_ignore_ascii_case("error") {
-            Ok(Key::Error)
-        } else if text.eq_ignore_ascii_case("id") {
-            Ok(Key::Id)
-        } else {
-            Err(Error::invalid_value(serde::de::Unexpected::Str(text), &self))
+        match Key::from_str(text) {
+            Some(key) => Ok(key),
+            None => Err(Error::invalid_value(serde::de::Unexpected::Str(text), &self)),
         }
     }
 }
@@ -43,3 +137,34 @@ impl<'a> Deserialize<'a> for Key {
         des.deserialize_str(KeyVisitor)
     }
 }
+
+///Wrapper around `params`, which accepts only array or object.
+pub struct Structured<P>(pub P);
+
+struct StructuredVisitor<P>(PhantomData<P>);
+
+impl<'a, P: Deserialize<'a>> Visitor<'a> for StructuredVisitor<P> {
+    type Value = Structured<P>;
+
+    #[inline]
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Params must be either array or object")
+    }
+
+    #[inline]
+    fn visit_seq<A: SeqAccess<'a>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        P::deserialize(SeqAccessDeserializer::new(seq)).map(Structured)
+    }
+
+    #[inline]
+    fn visit_map<A: MapAccess<'a>>(self, map: A) -> Result<Self::Value, A::Error> {
+        P::deserialize(MapAccessDeserializer::new(map)).map(Structured)
+    }
+}
+
+impl<'a, P: Deserialize<'a>> Deserialize<'a> for Structured<P> {
+    #[inline]
+    fn deserialize<D: Deserializer<'a>>(des: D) -> Result<Self, D::Error> {
+        des.deserialize_any(StructuredVisitor(PhantomData))
+    }
+}
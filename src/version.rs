@@ -4,12 +4,34 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use core::fmt;
 
 ///Protocol Version
+///
+///Version 1.0 has no `jsonrpc` member, hence it is never present in serialized messages.
+///During deserialization message without version is treated as v2.0, unless it is wrapped in `Legacy`,
+///which infers v1.0 from message's shape.
+///
+///Version 1.1 uses `version` member instead of `jsonrpc`, which is accepted only within `Legacy`.
 #[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
+#[non_exhaustive]
 pub enum Version {
+    ///v1.0
+    V1,
+    ///v1.1 working draft
+    V1_1,
     ///v2.0
     V2,
 }
 
+impl Version {
+    ///Returns textual representation of the version.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Version::V1 => "1.0",
+            Version::V1_1 => "1.1",
+            Version::V2 => "2.0",
+        }
+    }
+}
+
 impl Default for Version {
     #[inline(always)]
     fn default() -> Self {
@@ -19,9 +41,7 @@ impl Default for Version {
 
 impl Serialize for Version {
     fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
-        match *self {
-            Version::V2 => ser.serialize_str("2.0"),
-        }
+        ser.serialize_str(self.as_str())
     }
 }
 
@@ -38,12 +58,14 @@ impl<'a> Visitor<'a> for VersionVisitor {
 
     #[inline]
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("Identifier must be a string and be of one the following: ['2.0']")
+        formatter.write_str("Identifier must be a string and be of one the following: ['1.0', '1.1', '2.0']")
     }
 
     #[inline]
     fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
         match v {
+            "1.0" => Ok(Version::V1),
+            "1.1" => Ok(Version::V1_1),
             "2.0" => Ok(Version::V2),
             _ => Err(Error::invalid_value(serde::de::Unexpected::Str(v), &self))
         }
@@ -0,0 +1,57 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use json_rpc_types::{Id, Version, Error, ErrorCode, BuildError, Request, Response};
+
+const PING: Request<(), &str> = Request::new("ping");
+
+#[test]
+fn request_builder() {
+    let request = Request::<[u8; 2]>::try_new("update").unwrap().set_params([1, 2]).set_id(1);
+    assert_eq!(request.jsonrpc, Version::V2);
+    assert_eq!(request.method, "update");
+    assert_eq!(request.params, Some([1, 2]));
+    assert_eq!(request.id, Some(Id::Num(1)));
+    assert_eq!(serde_json::to_string(&request).unwrap(), r#"{"jsonrpc":"2.0","method":"update","params":[1,2],"id":1}"#);
+
+    let method = String::from("update");
+    let request = Request::<(), &str>::try_new(&method).unwrap().set_version(Version::V1_1);
+    assert_eq!(serde_json::to_string(&request).unwrap(), r#"{"version":"1.1","method":"update"}"#);
+
+    assert!(PING.is_notification());
+    assert_eq!(serde_json::to_string(&PING).unwrap(), r#"{"jsonrpc":"2.0","method":"ping"}"#);
+
+    let error = Request::<()>::try_new("subtract_subtract_subtract_subtract").unwrap_err();
+    assert_eq!(error, BuildError::Method { len: 35 });
+    assert_eq!(error.to_string(), "Method name of 35 bytes cannot be stored");
+}
+
+#[test]
+fn response_builder() {
+    let response = Response::<u8, ()>::ok(1).set_id(1);
+    assert_eq!(response, Response::result(Version::V2, 1, Some(Id::Num(1))));
+
+    let error = Error::try_with_text_message(ErrorCode::InvalidParams, "Expected two numbers").unwrap().set_data(2);
+    let response = Response::<u8, u8>::err(error).set_version(Version::V1).set_id(2);
+    assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"result":null,"error":{"code":-32602,"message":"Expected two numbers","data":2},"id":2}"#);
+
+    let error = Error::<()>::try_with_text_message(ErrorCode::InvalidParams, "Expected two numbers, but got three").unwrap_err();
+    assert_eq!(error, BuildError::Message { len: 35 });
+    assert_eq!(error.to_string(), "Error message of 35 bytes cannot be stored");
+}
+
+#[test]
+fn builder_should_set_id_from_text() {
+    let request = Request::<()>::try_new("update").unwrap().try_set_id("abc").unwrap();
+    assert_eq!(serde_json::to_string(&request).unwrap(), r#"{"jsonrpc":"2.0","method":"update","id":"abc"}"#);
+
+    let response = Response::<u8, ()>::ok(1).try_set_id("abc").unwrap();
+    assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"jsonrpc":"2.0","result":1,"id":"abc"}"#);
+
+    let response = Response::<u8, ()>::ok(1).try_set_id(2u64).unwrap();
+    assert_eq!(response.id, Some(Id::Num(2)));
+
+    let error = Request::<()>::try_new("update").unwrap().try_set_id("1234567890123456789012345678901234567").unwrap_err();
+    assert_eq!(error, BuildError::Id);
+    assert_eq!(error.to_string(), "Identifier cannot be stored");
+    assert_eq!(Response::<u8, ()>::ok(1).try_set_id("1234567890123456789012345678901234567").unwrap_err(), BuildError::Id);
+}
@@ -0,0 +1,118 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use json_rpc_types::{Canonical, CanonicalPolicy, Member, SpecOrder, Id, Version, Error, ErrorCode, Request, Response, Message};
+use json_rpc_types::str_buf::StrBuf;
+
+type Call = Request<[u32; 2]>;
+type Reply = Response<u32, u8>;
+
+struct NumericId;
+
+impl CanonicalPolicy for NumericId {
+    const REQUEST: [Member; 4] = [Member::Method, Member::Params, Member::Version, Member::Id];
+    const RESPONSE: [Member; 3] = [Member::Id, Member::Payload, Member::Version];
+    const NUMERIC_ID: bool = true;
+}
+
+fn canonical<T>(value: T) -> String where Canonical<T>: serde::Serialize {
+    serde_json::to_string(&Canonical::<T>::new(value)).unwrap()
+}
+
+#[test]
+fn canonical_request_should_have_fixed_member_order() {
+    let request = Call::new(StrBuf::from_str("sum")).set_params([1, 2]).set_id(1);
+    assert_eq!(canonical(&request), r#"{"jsonrpc":"2.0","id":1,"method":"sum","params":[1,2]}"#);
+    assert_eq!(serde_json::to_string(&Canonical::<_, SpecOrder>::new(&request)).unwrap(), r#"{"jsonrpc":"2.0","method":"sum","params":[1,2],"id":1}"#);
+
+    let notification = Call::new(StrBuf::from_str("ping"));
+    assert_eq!(canonical(&notification), r#"{"jsonrpc":"2.0","method":"ping"}"#);
+
+    let request = Call::new(StrBuf::from_str("sum")).set_version(Version::V1);
+    assert_eq!(canonical(&request), r#"{"id":null,"method":"sum","params":[]}"#);
+
+    let request = Call::new(StrBuf::from_str("sum")).set_version(Version::V1_1).set_id(Id::Str(StrBuf::from_str("a")));
+    assert_eq!(canonical(request), r#"{"version":"1.1","id":"a","method":"sum"}"#);
+}
+
+#[test]
+fn canonical_request_should_be_stable() {
+    let inputs = [
+        r#"{"jsonrpc":"2.0","method":"sum","params":[1,2],"id":7}"#,
+        r#"{"id":7,"params":[1,2],"method":"sum","jsonrpc":"2.0"}"#,
+        r#"{ "params" : [1, 2], "jsonrpc" : "2.0", "id" : 7, "method" : "sum" }"#,
+    ];
+
+    for input in inputs.iter() {
+        let request: Call = serde_json::from_str(input).unwrap();
+        assert_eq!(canonical(&request), r#"{"jsonrpc":"2.0","id":7,"method":"sum","params":[1,2]}"#);
+    }
+}
+
+#[test]
+fn canonical_response_should_be_stable() {
+    let inputs = [
+        r#"{"jsonrpc":"2.0","result":3,"id":7}"#,
+        r#"{"id":7,"result":3,"jsonrpc":"2.0"}"#,
+    ];
+
+    for input in inputs.iter() {
+        let response: Reply = serde_json::from_str(input).unwrap();
+        assert_eq!(canonical(&response), r#"{"jsonrpc":"2.0","id":7,"result":3}"#);
+    }
+
+    let response = Reply::error(Version::V2, Error::from_code(ErrorCode::InternalError).set_data(1), None);
+    assert_eq!(canonical(&response), r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32603,"message":"Internal error","data":1}}"#);
+
+    let response = Reply::result(Version::V1, 3, Some(Id::Num(1)));
+    assert_eq!(canonical(&response), r#"{"id":1,"result":3,"error":null}"#);
+
+    let message = Message::<[u32; 2], u32, u8>::Response(Reply::result(Version::V2, 3, Some(Id::Num(1))));
+    assert_eq!(canonical(message), r#"{"jsonrpc":"2.0","id":1,"result":3}"#);
+}
+
+#[test]
+fn canonical_should_normalize_numeric_id() {
+    let ids = [Id::Num(42), Id::Str(StrBuf::from_str("42")), Id::Str(StrBuf::from_str("0042"))];
+    for id in ids.iter() {
+        let request = Call::new(StrBuf::from_str("sum")).set_params([1, 2]).set_id(id.clone());
+        assert_eq!(serde_json::to_string(&Canonical::<_, NumericId>::new(&request)).unwrap(), r#"{"method":"sum","params":[1,2],"jsonrpc":"2.0","id":42}"#);
+
+        let response = Reply::result(Version::V2, 3, Some(id.clone()));
+        assert_eq!(serde_json::to_string(&Canonical::<_, NumericId>::new(&response)).unwrap(), r#"{"id":42,"result":3,"jsonrpc":"2.0"}"#);
+    }
+
+    let request = Call::new(StrBuf::from_str("sum")).set_id(Id::Str(StrBuf::from_str("x42")));
+    assert_eq!(serde_json::to_string(&Canonical::<_, NumericId>::new(&request)).unwrap(), r#"{"method":"sum","jsonrpc":"2.0","id":"x42"}"#);
+    assert_eq!(canonical(Call::new(StrBuf::from_str("sum")).set_id(Id::Str(StrBuf::from_str("42")))), r#"{"jsonrpc":"2.0","id":"42","method":"sum"}"#);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn canonical_should_sort_params() {
+    use json_rpc_types::IdFirst;
+
+    struct Sorted;
+
+    impl CanonicalPolicy for Sorted {
+        const REQUEST: [Member; 4] = IdFirst::REQUEST;
+        const RESPONSE: [Member; 3] = IdFirst::RESPONSE;
+        const SORT_PARAMS: bool = true;
+    }
+
+    #[derive(serde_derive::Serialize)]
+    struct Inner {
+        z: u8,
+        a: u8,
+    }
+
+    #[derive(serde_derive::Serialize)]
+    struct Params {
+        name: &'static str,
+        inner: Inner,
+        count: u8,
+    }
+
+    let request = Request::<Params>::new(StrBuf::from_str("set")).set_params(Params { name: "x", inner: Inner { z: 1, a: 2 }, count: 3 }).set_id(1);
+    assert_eq!(serde_json::to_string(&Canonical::<_, Sorted>::new(&request)).unwrap(), r#"{"jsonrpc":"2.0","id":1,"method":"set","params":{"count":3,"inner":{"a":2,"z":1},"name":"x"}}"#);
+    assert_eq!(canonical(&request), r#"{"jsonrpc":"2.0","id":1,"method":"set","params":{"name":"x","inner":{"z":1,"a":2},"count":3}}"#);
+}
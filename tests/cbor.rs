@@ -0,0 +1,132 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use json_rpc_types::{Id, Version, Error, ErrorCode, Request, Response, Message, Params, Legacy};
+use json_rpc_types::str_buf::StrBuf;
+
+use serde::{Serialize, de::DeserializeOwned};
+use ciborium::value::Value;
+
+use core::fmt;
+
+fn to_cbor<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(value, &mut bytes).expect("To serialize");
+    bytes
+}
+
+fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> T {
+    ciborium::de::from_reader(bytes).expect("To deserialize")
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    from_cbor(&to_cbor(value))
+}
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + fmt::Debug>(value: T) {
+    let bytes = to_cbor(&value);
+    let result: T = from_cbor(&bytes);
+    assert_eq!(result, value);
+}
+
+fn text(text: &str) -> Value {
+    Value::Text(text.to_owned())
+}
+
+#[test]
+fn cbor_should_encode_scalars() {
+    assert_eq!(to_value(&Version::V2), text("2.0"));
+    assert_eq!(to_value(&Version::V1_1), text("1.1"));
+    assert_eq!(to_value(&Version::V1), text("1.0"));
+    assert_eq!(to_value(&Id::Num(1)), Value::Integer(1.into()));
+    assert_eq!(to_value(&Id::Num(u64::MAX)), Value::Integer(u64::MAX.into()));
+    assert_eq!(to_value(&Id::Str(StrBuf::from_str("id"))), text("id"));
+    assert_eq!(to_value(&ErrorCode::MethodNotFound), Value::Integer((-32601).into()));
+    assert_eq!(to_value(&ErrorCode::ServerError(-32000)), Value::Integer((-32000).into()));
+
+    round_trip(Version::V2);
+    round_trip(Version::V1_1);
+    round_trip(Version::V1);
+    round_trip(Id::Num(0));
+    round_trip(Id::Num(u64::MAX));
+    round_trip(Id::Str(StrBuf::from_str("c0ffee")));
+    round_trip(ErrorCode::ParseError);
+    round_trip(ErrorCode::ServerError(1));
+    round_trip(ErrorCode::ServerError(i64::MIN));
+}
+
+#[test]
+fn cbor_should_round_trip_request() {
+    type Call = Request<Params<[u32; 2], ()>>;
+
+    let request = Call::new(StrBuf::from_str("sum")).set_params(Params::Positional([1, 2])).set_id(1);
+    assert_eq!(to_value(&request), Value::Map(vec![
+        (text("jsonrpc"), text("2.0")),
+        (text("method"), text("sum")),
+        (text("params"), Value::Array(vec![Value::Integer(1.into()), Value::Integer(2.into())])),
+        (text("id"), Value::Integer(1.into())),
+    ]));
+    round_trip(request);
+
+    round_trip(Call::new(StrBuf::from_str("notify")));
+    round_trip(Call::new(StrBuf::from_str("call")).set_id(Id::Str(StrBuf::from_str("id"))));
+    round_trip(Legacy(Call::new(StrBuf::from_str("v1")).set_version(Version::V1).set_params(Params::Positional([3, 4])).set_id(2)));
+    round_trip(Legacy(Call::new(StrBuf::from_str("v1_1")).set_version(Version::V1_1).set_id(3)));
+}
+
+#[test]
+fn cbor_should_round_trip_response() {
+    type Reply = Response<u32, u8>;
+
+    let response = Reply::result(Version::V2, 1, Some(Id::Num(1)));
+    assert_eq!(to_value(&response), Value::Map(vec![
+        (text("jsonrpc"), text("2.0")),
+        (text("result"), Value::Integer(1.into())),
+        (text("id"), Value::Integer(1.into())),
+    ]));
+    round_trip(response);
+
+    let response = Reply::error(Version::V2, Error::from_code(ErrorCode::InternalError).set_data(5), None);
+    assert_eq!(to_value(&response), Value::Map(vec![
+        (text("jsonrpc"), text("2.0")),
+        (text("error"), Value::Map(vec![
+            (text("code"), Value::Integer((-32603).into())),
+            (text("message"), text("Internal error")),
+            (text("data"), Value::Integer(5.into())),
+        ])),
+        (text("id"), Value::Null),
+    ]));
+    round_trip(response);
+
+    round_trip(Response::<(), u8>::result(Version::V2, (), Some(Id::Str(StrBuf::from_str("id")))));
+    round_trip(Legacy(Reply::result(Version::V1, 2, Some(Id::Num(2)))));
+    round_trip(Legacy(Reply::error(Version::V1, Error::from_code(ErrorCode::MethodNotFound), Some(Id::Num(3)))));
+    round_trip(Legacy(Reply::result(Version::V1_1, 3, Some(Id::Num(4)))));
+}
+
+#[test]
+fn cbor_should_round_trip_message() {
+    type Any = Message<[u32; 1], u32, ()>;
+
+    round_trip(Any::Request(Request::new(StrBuf::from_str("inc")).set_params([1]).set_id(1)));
+    round_trip(Any::Response(Response::result(Version::V2, 2, Some(Id::Num(1)))));
+}
+
+#[test]
+fn cbor_should_reject_invalid_id() {
+    let bytes = to_cbor(&Value::Integer((-1).into()));
+    assert!(ciborium::de::from_reader::<Id, _>(&bytes[..]).is_err());
+
+    let bytes = to_cbor(&Value::Bytes(vec![1, 2]));
+    assert!(ciborium::de::from_reader::<Id, _>(&bytes[..]).is_err());
+}
+
+#[test]
+fn cbor_should_recover_id() {
+    let bytes = to_cbor(&Value::Map(vec![
+        (text("method"), Value::Integer(1.into())),
+        (text("params"), Value::Bytes(vec![1])),
+        (text("id"), Value::Integer(7.into())),
+    ]));
+    let id: json_rpc_types::IdOnly = from_cbor(&bytes);
+    assert_eq!(id.into_inner(), Some(Id::Num(7)));
+}
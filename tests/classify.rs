@@ -0,0 +1,123 @@
+#![cfg(feature = "serde_json")]
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use core::convert::TryInto;
+use json_rpc_types::{parse_request, parse_dispatch, Dispatch, Id, ErrorCode, Version};
+
+type Request = json_rpc_types::Request<[i64; 2]>;
+
+fn reject(text: &str) -> (ErrorCode, &'static str, Option<Id>) {
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(text.as_bytes()).unwrap_err();
+    assert!(rejected.error.data.unwrap().starts_with(rejected.path()));
+    (rejected.error.code, rejected.path(), rejected.id)
+}
+
+#[test]
+fn parse_request_should_succeed() {
+    let text = r#"{"jsonrpc":"2.0","method":"subtract","params":[42,23],"id":1}"#;
+    let request: Request = parse_request(text.as_bytes()).unwrap();
+    assert_eq!(request.jsonrpc, Version::V2);
+    assert_eq!(request.params, Some([42, 23]));
+    assert_eq!(request.id, Some(Id::Num(1)));
+}
+
+#[test]
+fn parse_request_should_classify_parse_error() {
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract","id":1"#), (ErrorCode::ParseError, "", None));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract","id":1} x"#), (ErrorCode::ParseError, "", None));
+}
+
+#[test]
+fn parse_request_should_classify_invalid_request() {
+    assert_eq!(reject(r#"[1,2]"#), (ErrorCode::InvalidRequest, "", None));
+    assert_eq!(reject(r#"{"jsonrpc":"3.0","method":"subtract","id":1}"#), (ErrorCode::InvalidRequest, "jsonrpc", Some(Id::Num(1))));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":1,"id":"a"}"#), (ErrorCode::InvalidRequest, "method", Some(Id::Str("a".try_into().unwrap()))));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract_subtract_subtract_subtract","id":2}"#), (ErrorCode::InvalidRequest, "method", Some(Id::Num(2))));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","params":[1,2],"id":3}"#), (ErrorCode::InvalidRequest, "method", Some(Id::Num(3))));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract","params":1,"id":4}"#), (ErrorCode::InvalidRequest, "params", Some(Id::Num(4))));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract","id":true}"#), (ErrorCode::InvalidRequest, "id", None));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract","trace":1,"id":5}"#), (ErrorCode::InvalidRequest, "", Some(Id::Num(5))));
+}
+
+#[test]
+fn parse_request_should_classify_invalid_params() {
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract","params":[1],"id":1}"#), (ErrorCode::InvalidParams, "params", Some(Id::Num(1))));
+    assert_eq!(reject(r#"{"jsonrpc":"2.0","method":"subtract","params":{"a":1}}"#), (ErrorCode::InvalidParams, "params", None));
+}
+
+#[test]
+fn parse_dispatch_should_accept_params_before_method() {
+    #[derive(serde_derive::Deserialize, Debug, PartialEq)]
+    enum Methods {
+        #[serde(rename = "subtract")]
+        Subtract([i64; 2]),
+    }
+
+    type Dispatched = Dispatch<Methods, json_rpc_types::str_buf::StrBuf<32>>;
+
+    let request: Dispatched = parse_dispatch(br#"{"jsonrpc":"2.0","method":"subtract","params":[42,23],"id":1}"#).unwrap();
+    assert!(matches!(request, Dispatch::Method(ref request) if request.params == Some(Methods::Subtract([42, 23]))));
+
+    let request: Dispatched = parse_dispatch(br#"{"jsonrpc":"2.0","params":[42,23],"method":"subtract","id":1}"#).unwrap();
+    assert!(matches!(request, Dispatch::Method(ref request) if request.params == Some(Methods::Subtract([42, 23]))));
+
+    let request: Dispatched = parse_dispatch(br#"{"jsonrpc":"2.0","method":"subtract","params":[42],"id":2}"#).unwrap();
+    assert!(matches!(request, Dispatch::InvalidParams(ref request) if request.method == "subtract" && request.id == Some(Id::Num(2))));
+
+    let request: Dispatched = parse_dispatch(br#"{"jsonrpc":"2.0","params":[42],"method":"subtract","id":3}"#).unwrap();
+    assert!(matches!(request, Dispatch::InvalidParams(ref request) if request.method == "subtract" && request.id == Some(Id::Num(3))));
+
+    let rejected = parse_dispatch::<Methods, json_rpc_types::str_buf::StrBuf<32>>(br#"{"jsonrpc":"2.0","params":1,"method":"subtract","id":4}"#).unwrap_err();
+    assert_eq!((rejected.error.code, rejected.path(), rejected.id), (ErrorCode::InvalidRequest, "params", Some(Id::Num(4))));
+}
+
+#[test]
+fn rejected_should_convert_into_response() {
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(br#"{"jsonrpc":"2.0","method":1,"id":1}"#).unwrap_err();
+    let response = rejected.into_response::<()>();
+    let text = serde_json::to_string(&response).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","error":{"code":-32600,"message":"Invalid Request","data":"method: invalid type: integer `1`, expected a string buffer at line 1 column 27"},"id":1}"#);
+
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(br#"{"method":"subtract","params":[1],"id":2}"#).unwrap_err();
+    assert_eq!(rejected.jsonrpc, Version::V2);
+    let text = serde_json::to_string(&rejected.into_response::<()>()).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params","data":"params: invalid length 1, expected an array of length 2 at line 1 column 33"},"id":2}"#);
+
+    //JSON-RPCv1.1 `version` member is only accepted within `Legacy`
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(br#"{"version":"1.1","method":"subtract","params":[1,2],"id":3}"#).unwrap_err();
+    assert_eq!((rejected.jsonrpc, rejected.error.code, rejected.path()), (Version::V2, ErrorCode::InvalidRequest, "version"));
+}
+
+#[test]
+fn rejected_should_distinguish_notification_from_null_id() {
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(br#"{"jsonrpc":"2.0","method":"subtract","params":[1]}"#).unwrap_err();
+    assert_eq!((rejected.error.code, rejected.id.as_ref()), (ErrorCode::InvalidParams, None));
+    assert!(rejected.is_notification());
+    assert!(!rejected.has_id());
+
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(br#"{"jsonrpc":"2.0","method":"subtract","params":[1],"id":null}"#).unwrap_err();
+    assert_eq!((rejected.error.code, rejected.id.as_ref()), (ErrorCode::InvalidParams, None));
+    assert!(!rejected.is_notification());
+    assert!(rejected.has_id());
+
+    //`id` is recovered after invalid member
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(br#"{"jsonrpc":"2.0","method":1,"id":null}"#).unwrap_err();
+    assert_eq!((rejected.error.code, rejected.id.as_ref()), (ErrorCode::InvalidRequest, None));
+    assert!(rejected.has_id());
+    let rejected = parse_request::<[i64; 2], json_rpc_types::str_buf::StrBuf<32>>(br#"{"jsonrpc":"2.0","method":1}"#).unwrap_err();
+    assert!(rejected.is_notification());
+}
+
+#[test]
+fn parse_request_should_accept_null_params() {
+    let request: Request = parse_request(br#"{"jsonrpc":"2.0","method":"subtract","params":null,"id":1}"#).unwrap();
+    assert_eq!(request.params, None);
+    assert_eq!(request, serde_json::from_str(r#"{"jsonrpc":"2.0","method":"subtract","params":null,"id":1}"#).unwrap());
+}
+
+#[test]
+fn recover_id_should_ignore_invalid_members() {
+    assert_eq!(json_rpc_types::recover_id(br#"{"jsonrpc":1,"method":[],"id":7}"#), Some(Id::Num(7)));
+    assert_eq!(json_rpc_types::recover_id(br#"{"jsonrpc":1,"method":[],"id":7"#), None);
+    assert_eq!(json_rpc_types::recover_id(br#"{"jsonrpc":"2.0","id":true}"#), None);
+}
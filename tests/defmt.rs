@@ -0,0 +1,99 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+#![cfg(feature = "defmt")]
+
+use json_rpc_types::{Id, Version, Error, ErrorCode, Request, Response};
+
+use json_rpc_types::str_buf::StrBuf;
+
+use std::sync::Mutex;
+
+static OUTPUT: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+//Serializes tests, as logger is global.
+static LOCK: Mutex<()> = Mutex::new(());
+
+#[defmt::global_logger]
+struct Logger;
+
+unsafe impl defmt::Logger for Logger {
+    fn acquire() {}
+    unsafe fn flush() {}
+    unsafe fn release() {}
+    unsafe fn write(bytes: &[u8]) {
+        OUTPUT.lock().unwrap().extend_from_slice(bytes);
+    }
+}
+
+defmt::timestamp!("{=u32}", 0);
+
+fn assert_format<T: defmt::Format>() {
+}
+
+///Returns encoded frame of `value`.
+fn encode<T: defmt::Format>(value: &T) -> Vec<u8> {
+    let _lock = LOCK.lock().unwrap();
+    OUTPUT.lock().unwrap().clear();
+    defmt::println!("{}", value);
+    core::mem::take(&mut *OUTPUT.lock().unwrap())
+}
+
+fn contains(frame: &[u8], text: &str) -> bool {
+    frame.windows(text.len()).any(|window| window == text.as_bytes())
+}
+
+#[test]
+fn should_implement_format() {
+    assert_format::<Version>();
+    assert_format::<Id>();
+    assert_format::<ErrorCode>();
+    assert_format::<Error<u32>>();
+    assert_format::<Error<(), &'static str>>();
+    assert_format::<Request<[u32; 2]>>();
+    assert_format::<Request<(), &'static str>>();
+    assert_format::<Response<u32, ()>>();
+    assert_format::<Response<Option<u64>, u8, &'static str>>();
+}
+
+#[test]
+fn should_format_request() {
+    let request = Request::<[u32; 2]>::try_new("subtract").unwrap().set_params([42, 23]).set_id(1);
+    let frame = encode(&request);
+    assert!(contains(&frame, "subtract"));
+    assert!(contains(&frame, "2.0"));
+    assert_eq!(frame, encode(&request));
+
+    let other = Request::<[u32; 2]>::try_new("subtract").unwrap().set_params([42, 24]).set_id(1);
+    assert_ne!(frame, encode(&other));
+
+    let notification = Request::<(), &str>::new("notify").set_version(Version::V1_1);
+    let frame = encode(&notification);
+    assert!(contains(&frame, "notify"));
+    assert!(contains(&frame, "1.1"));
+}
+
+#[test]
+fn should_format_response() {
+    let response = Response::<u32, ()>::result(Version::V2, 19, Some(Id::Str(StrBuf::from_str("abc"))));
+    let frame = encode(&response);
+    assert!(contains(&frame, "2.0"));
+    assert!(contains(&frame, "abc"));
+    assert_ne!(frame, encode(&Response::<u32, ()>::result(Version::V2, 20, Some(Id::Str(StrBuf::from_str("abc"))))));
+
+    let response = Response::<u32, ()>::error(Version::V2, Error::from_code(ErrorCode::MethodNotFound), Some(Id::Num(1)));
+    let frame = encode(&response);
+    assert!(contains(&frame, "Method not found"));
+}
+
+#[test]
+fn should_format_error() {
+    let mut error = Error::<u32, &str>::with_text_message(ErrorCode::ServerError(-32000), "Disk is full");
+    error.data = Some(7);
+    let frame = encode(&error);
+    assert!(contains(&frame, "Disk is full"));
+    assert!(contains(&frame, "Server error"));
+    error.data = Some(8);
+    assert_ne!(frame, encode(&error));
+
+    let frame = encode(&Error::<(), &str>::with_text_message(ErrorCode::InternalError, "Oops"));
+    assert!(contains(&frame, "Oops"));
+    assert!(contains(&frame, "Internal error"));
+}
@@ -0,0 +1,142 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use json_rpc_types::{Dispatch, Id, Version, ErrorCode};
+use serde_derive::Deserialize;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Subtract {
+    minuend: i64,
+    subtrahend: i64,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Methods {
+    #[serde(rename = "subtract")]
+    Subtract(Subtract),
+    #[serde(rename = "sum", alias = "add")]
+    Sum(Vec<i64>),
+    #[serde(rename = "pow")]
+    Pow(i64, u32),
+    #[serde(rename = "log")]
+    Log(Option<Vec<String>>),
+    #[serde(rename = "ping")]
+    Ping,
+}
+
+type Request = Dispatch<Methods>;
+type Response = json_rpc_types::Response<i64, ()>;
+
+fn method(request: Request) -> json_rpc_types::Request<Methods> {
+    match request {
+        Dispatch::Method(request) => request,
+        Dispatch::InvalidParams(request) => panic!("Unexpected invalid params: {}", request.method),
+        Dispatch::NotFound(request) => panic!("Unexpected unknown method: {}", request.method),
+    }
+}
+
+#[test]
+fn dispatch_should_select_variant_by_method() {
+    let text = r#"{"jsonrpc":"2.0","method":"subtract","params":{"minuend":42,"subtrahend":23},"id":1}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.method, "subtract");
+    assert_eq!(request.params, Some(Methods::Subtract(Subtract { minuend: 42, subtrahend: 23 })));
+    assert_eq!(request.id, Some(Id::Num(1)));
+
+    let text = r#"{"jsonrpc":"2.0","method":"sum","params":[1,2,3],"id":2}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.params, Some(Methods::Sum(vec![1, 2, 3])));
+
+    let text = r#"{"jsonrpc":"2.0","method":"pow","params":[2,10],"id":3}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.params, Some(Methods::Pow(2, 10)));
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":[1,2],"id":4}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.params, Some(Methods::Sum(vec![1, 2])));
+}
+
+#[test]
+fn dispatch_should_handle_notification_without_params() {
+    let text = r#"{"jsonrpc":"2.0","method":"ping"}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert!(request.is_notification());
+    assert_eq!(request.params, Some(Methods::Ping));
+
+    let text = r#"{"jsonrpc":"2.0","method":"log"}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.params, Some(Methods::Log(None)));
+
+    let text = r#"{"jsonrpc":"2.0","method":"ping","params":[]}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.params, Some(Methods::Ping));
+}
+
+#[test]
+fn dispatch_should_map_unknown_method_to_not_found() {
+    let text = r#"{"jsonrpc":"2.0","method":"divide","params":[42,23],"id":1}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    match request {
+        Dispatch::NotFound(ref request) => {
+            assert_eq!(request.method, "divide");
+            assert_eq!(request.params, None);
+        },
+        Dispatch::Method(_) | Dispatch::InvalidParams(_) => panic!("Unexpected method"),
+    }
+    let response = request.into_request::<i64, ()>().unwrap_err().unwrap();
+    assert_eq!(response, Response::error(Version::V2, json_rpc_types::Error::from_code(ErrorCode::MethodNotFound), Some(Id::Num(1))));
+
+    let text = r#"{"jsonrpc":"2.0","method":"divide"}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert!(request.into_request::<i64, ()>().unwrap_err().is_none());
+}
+
+#[test]
+fn dispatch_should_fail_on_invalid_params() {
+    let text = r#"{"jsonrpc":"2.0","method":"pow","params":["2",10],"id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: string \"2\", expected i64 at line 1 column 45");
+
+    let text = r#"{"jsonrpc":"2.0","method":"pow","id":2}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert!(matches!(request, Dispatch::InvalidParams(ref request) if request.method == "pow"));
+    let response = request.into_request::<i64, ()>().unwrap_err().unwrap();
+    assert_eq!(response, Response::error(Version::V2, json_rpc_types::Error::from_code(ErrorCode::InvalidParams), Some(Id::Num(2))));
+}
+
+#[cfg(not(feature = "serde_json"))]
+#[test]
+fn dispatch_should_fail_on_params_preceding_method() {
+    let text = r#"{"jsonrpc":"2.0","params":[2,10],"method":"pow","id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC Request `params` must follow `method` at line 1 column 25");
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn dispatch_should_buffer_params_preceding_method() {
+    let text = r#"{"params":[2,10],"id":1,"method":"pow","jsonrpc":"2.0"}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.method, "pow");
+    assert_eq!(request.params, Some(Methods::Pow(2, 10)));
+    assert_eq!(request.id, Some(Id::Num(1)));
+
+    let text = r#"{"params":{"subtrahend":23,"minuend":42},"method":"subtract"}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.params, Some(Methods::Subtract(Subtract { minuend: 42, subtrahend: 23 })));
+
+    let text = r#"{"params":null,"method":"log"}"#;
+    let request = method(serde_json::from_str(text).unwrap());
+    assert_eq!(request.params, Some(Methods::Log(None)));
+
+    let text = r#"{"params":[42,23],"method":"divide","id":2}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert!(matches!(request, Dispatch::NotFound(ref request) if request.method == "divide" && request.params.is_none()));
+
+    let text = r#"{"params":["2",10],"method":"pow","id":3}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert!(matches!(request, Dispatch::InvalidParams(ref request) if request.method == "pow" && request.id == Some(Id::Num(3))));
+
+    let text = r#"{"params":[2,10],"params":[2,10],"method":"pow"}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert!(error.to_string().starts_with("duplicate field `params`"));
+}
@@ -1,7 +1,7 @@
 #![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
 
 type Error = json_rpc_types::Error<()>;
-use json_rpc_types::ErrorCode;
+use json_rpc_types::{ErrorCode, BuildError};
 
 #[test]
 fn error_should_truncated_message() {
@@ -34,3 +34,21 @@ fn error_should_not_panic_message_within_limit() {
     assert_eq!(error.message.len(), 30);
     assert_eq!(error.message, &message[..30]);
 }
+
+#[test]
+fn error_try_with_text_message_should_fail_on_overflow() {
+    let message = "12345678912345678912345678912345";
+    assert_eq!(Error::try_with_text_message(ErrorCode::ParseError, message), Err(BuildError::Message { len: 32 }));
+    let error = Error::try_with_text_message(ErrorCode::ParseError, &message[..31]).unwrap();
+    assert_eq!(error.message, &message[..31]);
+    let error = Error::try_with_text_message(ErrorCode::ParseError, "").unwrap();
+    assert_eq!(error.message, "");
+}
+
+#[test]
+fn error_should_accept_empty_message() {
+    let error = Error::with_custom_msg(ErrorCode::ParseError, "");
+    assert_eq!(error.message, "");
+    let error = Error::with_custom_msg_truncated(ErrorCode::ParseError, "");
+    assert_eq!(error.message, "");
+}
@@ -0,0 +1,105 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use core::convert::TryInto;
+use json_rpc_types::{Extended, Id, Version, Error, ErrorCode};
+
+type Request = json_rpc_types::Request<Vec<serde_json::Value>>;
+type Response = json_rpc_types::Response<serde_json::Value, serde_json::Value>;
+
+#[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+struct Meta {
+    trace: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token: Option<String>,
+}
+
+fn create_request() -> Request {
+    Request {
+        jsonrpc: Version::V2,
+        method: "update".try_into().unwrap(),
+        params: Some(vec![serde_json::Value::from(1), serde_json::Value::from(2)]),
+        id: Some(Id::Num(1)),
+    }
+}
+
+#[test]
+fn extended_request_serialize() {
+    let request = Extended::new(create_request(), Meta {
+        trace: "00-abc-01".to_owned(),
+        token: None,
+    });
+
+    let serialized = serde_json::to_string(&request).unwrap();
+    assert_eq!(serialized, r#"{"jsonrpc":"2.0","method":"update","params":[1,2],"id":1,"trace":"00-abc-01"}"#);
+
+    let request = Extended::new(create_request(), ());
+    let serialized = serde_json::to_string(&request).unwrap();
+    assert_eq!(serialized, r#"{"jsonrpc":"2.0","method":"update","params":[1,2],"id":1}"#);
+
+    let request = Extended::new(create_request(), 1);
+    let error = serde_json::to_string(&request).unwrap_err();
+    assert_eq!(error.to_string(), "Extensions must be either map or struct");
+}
+
+#[test]
+fn extended_request_deserialize() {
+    let text = r#"{"trace":"00-abc-01","jsonrpc":"2.0","method":"update","params":[1,2],"token":"secret","id":1}"#;
+    let request: Extended<Request, Meta> = serde_json::from_str(text).unwrap();
+    assert_eq!(request.message, create_request());
+    assert_eq!(request.extensions, Meta {
+        trace: "00-abc-01".to_owned(),
+        token: Some("secret".to_owned()),
+    });
+
+    let request: Extended<Request, serde_json::Map<String, serde_json::Value>> = serde_json::from_str(text).unwrap();
+    assert_eq!(request.message, create_request());
+    assert_eq!(request.extensions.len(), 2);
+    assert_eq!(request.extensions["trace"], "00-abc-01");
+    assert_eq!(request.extensions["token"], "secret");
+
+    let text = r#"{"jsonrpc":"2.0","method":"update","params":[1,2],"id":1}"#;
+    let request: Extended<Request> = serde_json::from_str(text).unwrap();
+    assert_eq!(request.message, create_request());
+
+    let text = r#"{"jsonrpc":"2.0","method":"update","params":[1,2],"id":1,"trace":"00-abc-01"}"#;
+    let error = serde_json::from_str::<Extended<Request>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "unknown field `trace`, expected one of `jsonrpc`, `version`, `method`, `params`, `id` at line 1 column 64");
+
+    let text = r#"{"jsonrpc":"2.0","params":[1,2],"id":1,"trace":"00-abc-01"}"#;
+    let error = serde_json::from_str::<Extended<Request, Meta>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `method` at line 1 column 59");
+}
+
+#[test]
+fn extended_response_serialize() {
+    let response = Extended::new(Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(1))), serde_json::json!({
+        "meta": {"node": 1}
+    }));
+
+    let serialized = serde_json::to_string(&response).unwrap();
+    assert_eq!(serialized, r#"{"jsonrpc":"2.0","result":1,"id":1,"meta":{"node":1}}"#);
+}
+
+#[test]
+fn extended_response_deserialize() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    #[allow(non_snake_case)]
+    struct Timing {
+        usIn: u64,
+        usOut: u64,
+    }
+
+    let text = r#"{"jsonrpc":"2.0","usIn":1,"error":{"code":-32601,"message":"Method not found"},"id":1,"usOut":2}"#;
+    let response: Extended<Response, Timing> = serde_json::from_str(text).unwrap();
+    assert_eq!(response.message, Response::error(Version::V2, Error::from_code(ErrorCode::MethodNotFound), Some(Id::Num(1))));
+    assert_eq!(response.extensions, Timing {
+        usIn: 1,
+        usOut: 2,
+    });
+
+    let error = serde_json::from_str::<Extended<Response>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "unknown field `usIn`, expected one of `jsonrpc`, `version`, `result`, `error`, `id` at line 1 column 23");
+
+    let response: Extended<Response, Option<serde::de::IgnoredAny>> = serde_json::from_str(text).unwrap();
+    assert_eq!(response.message, Response::error(Version::V2, Error::from_code(ErrorCode::MethodNotFound), Some(Id::Num(1))));
+}
@@ -0,0 +1,205 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+#![cfg(feature = "framing")]
+
+use json_rpc_types::{Framing, Encoding, Checksum, FrameError, FrameDecoder};
+
+const COBS: Framing = Framing::new(Encoding::Cobs, Checksum::None);
+const SLIP: Framing = Framing::new(Encoding::Slip, Checksum::None);
+
+fn decode<const N: usize>(decoder: &mut FrameDecoder<N>, input: &[u8], out: &mut [u8]) -> Option<Result<usize, FrameError>> {
+    let mut result = None;
+    for byte in input {
+        if let Some(frame) = decoder.push(*byte) {
+            assert!(result.is_none(), "Multiple frames");
+            result = Some(frame.map(|frame| {
+                out[..frame.len()].copy_from_slice(frame);
+                frame.len()
+            }));
+        }
+    }
+    result
+}
+
+fn round_trip(framing: Framing, payload: &[u8]) {
+    let mut frame = [0u8; 2048];
+    let len = framing.encode(payload, &mut frame).unwrap();
+    assert!(len <= framing.max_encoded_len(payload.len()));
+
+    let mut decoder = FrameDecoder::<600>::new(framing);
+    let mut out = [0u8; 600];
+    let decoded = decode(&mut decoder, &frame[..len], &mut out).unwrap().unwrap();
+    assert_eq!(&out[..decoded], payload);
+}
+
+#[test]
+fn should_compute_crc() {
+    assert_eq!(Checksum::crc16(b"123456789"), 0x29B1);
+    assert_eq!(Checksum::crc32(b"123456789"), 0xCBF43926);
+}
+
+#[test]
+fn should_encode_cobs() {
+    let mut frame = [0u8; 600];
+
+    let len = COBS.encode(&[0x00], &mut frame).unwrap();
+    assert_eq!(&frame[..len], [0x01, 0x01, 0x00]);
+
+    let len = COBS.encode(&[0x11, 0x22, 0x00, 0x33], &mut frame).unwrap();
+    assert_eq!(&frame[..len], [0x03, 0x11, 0x22, 0x02, 0x33, 0x00]);
+
+    let mut payload = [0u8; 255];
+    for (idx, byte) in payload.iter_mut().enumerate() {
+        *byte = idx as u8 + 1;
+    }
+
+    let len = COBS.encode(&payload[..254], &mut frame).unwrap();
+    assert_eq!(len, 256);
+    assert_eq!(frame[0], 0xFF);
+    assert_eq!(&frame[1..255], &payload[..254]);
+    assert_eq!(frame[255], 0x00);
+
+    let len = COBS.encode(&payload, &mut frame).unwrap();
+    assert_eq!(len, 258);
+    assert_eq!(frame[0], 0xFF);
+    assert_eq!(&frame[255..258], [0x02, 0xFF, 0x00]);
+}
+
+#[test]
+fn should_encode_slip() {
+    let mut frame = [0u8; 16];
+
+    let len = SLIP.encode(&[0x01, 0xC0, 0x02, 0xDB], &mut frame).unwrap();
+    assert_eq!(&frame[..len], [0x01, 0xDB, 0xDC, 0x02, 0xDB, 0xDD, 0xC0]);
+}
+
+#[test]
+fn should_round_trip_frames() {
+    let mut payload = [0u8; 520];
+    for (idx, byte) in payload.iter_mut().enumerate() {
+        *byte = (idx % 7) as u8 * 0x20;
+    }
+
+    for encoding in [Encoding::Cobs, Encoding::Slip] {
+        for checksum in [Checksum::None, Checksum::Crc16, Checksum::Crc32] {
+            let framing = Framing::new(encoding, checksum);
+            //Empty SLIP frame is indistinguishable from terminator.
+            if encoding != Encoding::Slip || checksum != Checksum::None {
+                round_trip(framing, b"");
+            }
+            round_trip(framing, &[0x00]);
+            round_trip(framing, &[0xC0, 0xDB, 0x00, 0xFF]);
+            round_trip(framing, &[0xFF; 254]);
+            round_trip(framing, &[0xFF; 255]);
+            round_trip(framing, &payload);
+            round_trip(framing, &[0xC0; 300]);
+        }
+    }
+}
+
+#[test]
+fn should_fail_encoding_into_small_buffer() {
+    let mut frame = [0u8; 8];
+    assert_eq!(SLIP.encode(b"abcd", &mut frame), Err(FrameError::Overflow));
+    assert_eq!(COBS.encode(b"abcdefg", &mut frame), Err(FrameError::Overflow));
+}
+
+#[test]
+fn should_decode_consecutive_frames() {
+    let framing = Framing::new(Encoding::Slip, Checksum::Crc16);
+    let mut stream = [0u8; 64];
+    let mut len = 0;
+    stream[0] = 0xC0;
+    len += 1;
+    len += framing.encode(b"first", &mut stream[len..]).unwrap();
+    len += framing.encode(b"second", &mut stream[len..]).unwrap();
+
+    let mut decoder = FrameDecoder::<16>::new(framing);
+    let mut frames = 0;
+    for byte in &stream[..len] {
+        if let Some(frame) = decoder.push(*byte) {
+            let expected: &[u8] = match frames {
+                0 => b"first",
+                _ => b"second",
+            };
+            assert_eq!(frame, Ok(expected));
+            frames += 1;
+        }
+    }
+    assert_eq!(frames, 2);
+}
+
+#[test]
+fn should_report_invalid_frames() {
+    let framing = Framing::new(Encoding::Cobs, Checksum::Crc32);
+    let mut frame = [0u8; 32];
+    let len = framing.encode(b"payload", &mut frame).unwrap();
+    frame[3] ^= 0x01;
+
+    let mut decoder = FrameDecoder::<32>::new(framing);
+    let mut out = [0u8; 32];
+    assert_eq!(decode(&mut decoder, &frame[..len], &mut out), Some(Err(FrameError::Checksum)));
+
+    //Block claims more bytes than frame contains.
+    assert_eq!(decode(&mut decoder, &[0x05, 0x01, 0x00], &mut out), Some(Err(FrameError::Malformed)));
+
+    let mut decoder = FrameDecoder::<4>::new(SLIP);
+    assert_eq!(decode(&mut decoder, b"overflow\xC0", &mut out), Some(Err(FrameError::Overflow)));
+    assert_eq!(decode(&mut decoder, b"\xDB\x01\xC0", &mut out), Some(Err(FrameError::Malformed)));
+    //Decoder recovers after error.
+    assert_eq!(decode(&mut decoder, b"ok\xC0", &mut out), Some(Ok(2)));
+    assert_eq!(&out[..2], b"ok");
+}
+
+#[test]
+fn should_end_slip_frame_on_escaped_terminator() {
+    let mut decoder = FrameDecoder::<16>::new(SLIP);
+    let mut out = [0u8; 16];
+
+    //Escaped terminator ends malformed frame, rather than swallowing the next one.
+    assert_eq!(decode(&mut decoder, b"bad\xDB\xC0", &mut out), Some(Err(FrameError::Malformed)));
+    assert_eq!(decode(&mut decoder, b"good\xC0", &mut out), Some(Ok(4)));
+    assert_eq!(&out[..4], b"good");
+
+    let mut stream = [0u8; 32];
+    let mut len = 0;
+    stream[..2].copy_from_slice(b"\xDB\xC0");
+    len += 2;
+    len += SLIP.encode(b"next", &mut stream[len..]).unwrap();
+
+    let mut decoder = FrameDecoder::<16>::new(SLIP);
+    let mut frames = [None, None];
+    let mut count = 0;
+    for byte in &stream[..len] {
+        if let Some(frame) = decoder.push(*byte) {
+            frames[count] = Some(frame.map(|frame| frame.len()));
+            count += 1;
+        }
+    }
+    assert_eq!(frames, [Some(Err(FrameError::Malformed)), Some(Ok(4))]);
+}
+
+#[cfg(feature = "serde-json-core")]
+#[test]
+fn should_serialize_messages_into_frame() {
+    use json_rpc_types::{Request, Response, Version, Id};
+
+    for encoding in [Encoding::Cobs, Encoding::Slip] {
+        let framing = Framing::new(encoding, Checksum::Crc16);
+        let mut frame = [0u8; 256];
+        let mut decoder = FrameDecoder::<128>::new(framing);
+        let mut out = [0u8; 128];
+
+        let request = Request::<[u32; 2], &str>::new("sum").set_params([1, 2]).set_id(1);
+        let len = request.to_frame(&framing, &mut frame).unwrap();
+        let decoded = decode(&mut decoder, &frame[..len], &mut out).unwrap().unwrap();
+        assert_eq!(&out[..decoded], br#"{"jsonrpc":"2.0","method":"sum","params":[1,2],"id":1}"#);
+
+        let response = Response::<u32, ()>::result(Version::V2, 3, Some(Id::Num(1)));
+        let len = response.to_frame(&framing, &mut frame).unwrap();
+        let decoded = decode(&mut decoder, &frame[..len], &mut out).unwrap().unwrap();
+        assert_eq!(&out[..decoded], br#"{"jsonrpc":"2.0","result":3,"id":1}"#);
+
+        let mut small = [0u8; 40];
+        assert_eq!(request.to_frame(&framing, &mut small), Err(FrameError::Overflow));
+    }
+}
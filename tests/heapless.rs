@@ -0,0 +1,79 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+#![cfg(all(feature = "heapless", feature = "serde-json-core"))]
+
+use json_rpc_types::{Id, Version, Error, ErrorCode, BuildError, Request, Response, RequestBatch, ResponseBatch, ArrayParams, MaxSize};
+use json_rpc_types::heapless::String;
+
+type Method = String<16>;
+type Call = Request<ArrayParams<u32, 4>, Method>;
+
+#[test]
+fn should_round_trip_heapless_request() {
+    let mut params = ArrayParams::<u32, 4>::new();
+    params.extend_from_slice(&[1, 2, 3]).unwrap();
+    let request = Call::new("sum".parse().unwrap()).set_params(params);
+
+    let mut buffer = [0u8; <Call as MaxSize>::MAX_SIZE];
+    let len = request.to_slice(&mut buffer).unwrap();
+    assert_eq!(&buffer[..len], br#"{"jsonrpc":"2.0","method":"sum","params":[1,2,3]}"#);
+
+    let (result, read) = Call::from_slice(&buffer[..len]).unwrap();
+    assert_eq!(read, len);
+    assert_eq!(result, request);
+}
+
+#[test]
+fn should_reject_overflowing_heapless_params() {
+    let input = br#"{"jsonrpc":"2.0","method":"sum","params":[1,2,3,4,5]}"#;
+    assert!(Call::from_slice(input).is_err());
+
+    let input = br#"{"jsonrpc":"2.0","method":"method_name_is_too_long"}"#;
+    assert!(Call::from_slice(input).is_err());
+
+    let overlong: Result<Method, ()> = "method_name_is_too_long".parse();
+    assert!(overlong.is_err());
+}
+
+#[test]
+fn should_round_trip_request_batch() {
+    let mut batch = RequestBatch::<[u32; 1], 2, Method>::new();
+    batch.push(Request::new("first".parse().unwrap()).set_params([1])).unwrap();
+    batch.push(Request::new("second".parse().unwrap()).set_params([2])).unwrap();
+
+    let mut buffer = [0u8; <RequestBatch<[u32; 1], 2, Method> as MaxSize>::MAX_SIZE];
+    let len = serde_json_core::to_slice(&batch, &mut buffer).unwrap();
+    let expected = br#"[{"jsonrpc":"2.0","method":"first","params":[1]},{"jsonrpc":"2.0","method":"second","params":[2]}]"#;
+    assert_eq!(&buffer[..len], expected);
+
+    let (result, _) = serde_json_core::from_slice::<RequestBatch<[u32; 1], 2, Method>>(expected).unwrap();
+    assert_eq!(result, batch);
+
+    let input = br#"[{"jsonrpc":"2.0","method":"first"},{"jsonrpc":"2.0","method":"second"},{"jsonrpc":"2.0","method":"third"}]"#;
+    assert!(serde_json_core::from_slice::<RequestBatch<[u32; 1], 2, Method>>(input).is_err());
+}
+
+#[test]
+fn should_serialize_heapless_error_message() {
+    type Message = String<32>;
+
+    let mut batch = ResponseBatch::<u32, (), 2, Message>::new();
+    batch.push(Response::result(Version::V2, 1, Some(Id::Num(1)))).unwrap();
+    batch.push(Response::error(Version::V2, Error::with_text_message(ErrorCode::MethodNotFound, ErrorCode::MethodNotFound.message()), Some(Id::Num(2)))).unwrap();
+
+    let mut buffer = [0u8; <ResponseBatch<u32, (), 2, Message> as MaxSize>::MAX_SIZE];
+    let len = serde_json_core::to_slice(&batch, &mut buffer).unwrap();
+    assert_eq!(&buffer[..len], br#"[{"jsonrpc":"2.0","result":1,"id":1},{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":2}]"#);
+
+    let error = Error::<(), Message>::try_with_text_message(ErrorCode::InternalError, "Internal error").unwrap();
+    assert_eq!(error.message.as_str(), "Internal error");
+}
+
+#[test]
+fn should_fail_to_build_overflowing_heapless_text() {
+    let request = Call::try_new("sum").unwrap();
+    assert_eq!(request.method.as_str(), "sum");
+    assert_eq!(Call::try_new("method_name_is_too_long").unwrap_err(), BuildError::Method { len: 23 });
+
+    let error = Error::<(), String<8>>::try_with_text_message(ErrorCode::InternalError, "Internal error").unwrap_err();
+    assert_eq!(error, BuildError::Message { len: 14 });
+}
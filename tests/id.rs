@@ -1,6 +1,7 @@
 #![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
 
-use json_rpc_types::Id;
+use core::convert::TryFrom;
+use json_rpc_types::{Id, IdOnly, ParseIdError};
 
 type StrBuf = str_buf::StrBuf<36>;
 
@@ -56,3 +57,157 @@ fn id_serialization() {
     let serialized = serde_json::to_string(&d).unwrap();
     assert_eq!(serialized, r#"[0,2,3,"3","test"]"#);
 }
+
+#[test]
+fn id_only_should_recover_id_from_invalid_request() {
+    let text = r#"{"jsonrpc":"3.0","method":"subtract_subtract_subtract_subtract","params":1,"id":1}"#;
+    let id: IdOnly = serde_json::from_str(text).unwrap();
+    assert_eq!(id, IdOnly(Some(Id::Num(1))));
+
+    let text = r#"{"method":{"id":2},"id":"3"}"#;
+    let id: IdOnly = serde_json::from_str(text).unwrap();
+    assert_eq!(id.into_inner(), Some(Id::Str(StrBuf::from_str("3"))));
+
+    let text = r#"{"jsonrpc":"2.0","method":"update"}"#;
+    let id: IdOnly = serde_json::from_str(text).unwrap();
+    assert_eq!(id, IdOnly(None));
+
+    let text = r#"{"jsonrpc":"2.0","id":[1,2]}"#;
+    let id: IdOnly = serde_json::from_str(text).unwrap();
+    assert_eq!(id, IdOnly(None));
+
+    let text = r#"{"jsonrpc":"2.0","id":-1}"#;
+    let id: IdOnly = serde_json::from_str(text).unwrap();
+    assert_eq!(id, IdOnly(None));
+
+    let text = r#"{"jsonrpc":"2.0","id":1,"id":null}"#;
+    let id: IdOnly = serde_json::from_str(text).unwrap();
+    assert_eq!(id, IdOnly(Some(Id::Num(1))));
+
+    assert!(serde_json::from_str::<IdOnly>("[1]").is_err());
+}
+
+#[test]
+fn id_conversions() {
+    assert_eq!(Id::from(1), Id::Num(1));
+    assert_eq!(Id::from(StrBuf::from_str("abc")), Id::Str(StrBuf::from_str("abc")));
+    assert_eq!(Id::try_from("abc").unwrap(), Id::Str(StrBuf::from_str("abc")));
+    assert_eq!(Id::try_from("42").unwrap(), Id::Str(StrBuf::from_str("42")));
+    assert_eq!(Id::try_from(r#""1""#).unwrap(), Id::Str(StrBuf::from_str(r#""1""#)));
+    assert_eq!(Id::try_from("123456789012345678901234567890123456").unwrap(), Id::Str(StrBuf::from_str("123456789012345678901234567890123456")));
+    assert_eq!(Id::try_from("1234567890123456789012345678901234567"), Err(ParseIdError::Overflow));
+
+    assert_eq!("1".parse::<Id>().unwrap(), Id::Num(1));
+    assert_eq!(r#""1""#.parse::<Id>().unwrap(), Id::Str(StrBuf::from_str("1")));
+    assert_eq!(r#""a\"b\u00e9\ud83d\ude00""#.parse::<Id>().unwrap(), Id::Str(StrBuf::from_str("a\"b\u{e9}\u{1f600}")));
+    assert_eq!("-1".parse::<Id>(), Err(ParseIdError::Invalid));
+    assert_eq!("abc".parse::<Id>(), Err(ParseIdError::Invalid));
+    assert_eq!("18446744073709551616".parse::<Id>(), Err(ParseIdError::Invalid));
+    assert_eq!(r#""a"b""#.parse::<Id>(), Err(ParseIdError::Invalid));
+    assert_eq!(r#""a\x""#.parse::<Id>(), Err(ParseIdError::Invalid));
+    assert_eq!(r#"""#.parse::<Id>(), Err(ParseIdError::Invalid));
+    assert_eq!(r#""1234567890123456789012345678901234567""#.parse::<Id>(), Err(ParseIdError::Overflow));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn id_should_convert_from_string() {
+    assert_eq!(Id::try_from(String::from("abc")).unwrap(), Id::Str(StrBuf::from_str("abc")));
+    assert_eq!(Id::try_from(String::from("42")).unwrap(), Id::Str(StrBuf::from_str("42")));
+    assert_eq!(Id::try_from("1".repeat(37)), Err(ParseIdError::Overflow));
+}
+
+#[test]
+fn id_display_should_match_json() {
+    let ids = [Id::Num(1), Id::Str(StrBuf::from_str("abc")), Id::Str(StrBuf::from_str("a\"b\\c\n\u{1}"))];
+    for id in ids.iter() {
+        assert_eq!(id.to_string(), serde_json::to_string(id).unwrap());
+    }
+}
+
+#[test]
+fn id_display_should_round_trip_via_parse() {
+    let ids = [
+        Id::Num(0),
+        Id::Num(u64::MAX),
+        Id::Str(StrBuf::from_str("")),
+        Id::Str(StrBuf::from_str("1")),
+        Id::Str(StrBuf::from_str("abc")),
+        Id::Str(StrBuf::from_str("a\"b\\c\n\r\t\u{1}\u{e9}")),
+    ];
+    for id in ids.iter() {
+        assert_eq!(id.to_string().parse::<Id>().as_ref(), Ok(id));
+    }
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn id_from_uuid() {
+    let id = Id::from(uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8));
+    assert_eq!(id, Id::Str(StrBuf::from_str("67e55044-10b1-426f-9247-bb680e5fe0c8")));
+}
+
+#[cfg(feature = "ulid")]
+#[test]
+fn id_from_ulid() {
+    let id = Id::from(ulid::Ulid(0x01_7D_8A_6E_6A_7E_3B_1C_2B_4F_5A_6E_7D_8C_9B_0A));
+    assert_eq!(id.to_string(), format!("\"{}\"", ulid::Ulid(0x01_7D_8A_6E_6A_7E_3B_1C_2B_4F_5A_6E_7D_8C_9B_0A)));
+}
+
+#[test]
+fn id_should_order_by_content() {
+    use std::collections::BTreeMap;
+
+    assert!(Id::Num(1) < Id::Num(2));
+    assert!(Id::Num(u64::MAX) < Id::Str(StrBuf::from_str("0")));
+    assert!(Id::Str(StrBuf::from_str("a")) < Id::Str(StrBuf::from_str("ab")));
+    assert!(Id::Str(StrBuf::from_str("ab")) < Id::Str(StrBuf::from_str("b")));
+
+    let mut map = BTreeMap::new();
+    map.insert(Id::Str(StrBuf::from_str("b")), 3);
+    map.insert(Id::Num(10), 2);
+    map.insert(Id::Num(2), 1);
+    assert_eq!(map.values().copied().collect::<Vec<_>>(), [1, 2, 3]);
+}
+
+#[test]
+fn id_binary_encoding() {
+    let ids = [
+        Id::Num(0),
+        Id::Num(127),
+        Id::Num(128),
+        Id::Num(300),
+        Id::Num(u64::MAX),
+        Id::Str(StrBuf::from_str("")),
+        Id::Str(StrBuf::from_str("abc")),
+        Id::Str(StrBuf::from_str("67e55044-10b1-426f-9247-bb680e5fe0c8")),
+    ];
+
+    for id in ids.iter() {
+        let mut buffer = [0u8; Id::MAX_ENCODED_LEN + 1];
+        let len = id.encode(&mut buffer).unwrap();
+        assert_eq!(len, id.encoded_len());
+        assert_eq!(Id::decode(&buffer[..len]), Some((id.clone(), len)));
+        assert_eq!(Id::decode(&buffer), Some((id.clone(), len)));
+        assert_eq!(Id::decode(&buffer[..len - 1]), None);
+        assert_eq!(id.encode(&mut buffer[..len - 1]), None);
+    }
+
+    let mut buffer = [0u8; Id::MAX_ENCODED_LEN];
+    assert_eq!(Id::Num(300).encode(&mut buffer), Some(3));
+    assert_eq!(&buffer[..3], &[0, 0xac, 0x02]);
+    assert_eq!(Id::Str(StrBuf::from_str("ab")).encode(&mut buffer), Some(4));
+    assert_eq!(&buffer[..4], &[1, 2, b'a', b'b']);
+
+    assert_eq!(Id::decode(&[]), None);
+    assert_eq!(Id::decode(&[2, 0]), None);
+    assert_eq!(Id::decode(&[1, 2, 0xff, 0xff]), None);
+    assert_eq!(Id::decode(&[0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]), None);
+    assert_eq!(Id::decode(&[0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]), Some((Id::Num(u64::MAX), 11)));
+    //Non-canonical varints, that `encode` never produces
+    assert_eq!(Id::decode(&[0, 0x80, 0x00]), None);
+    assert_eq!(Id::decode(&[0, 0x81, 0x80, 0x00]), None);
+    assert_eq!(Id::decode(&[0, 0x00]), Some((Id::Num(0), 2)));
+    assert_eq!(Id::decode(&[0, 0x81, 0x00]), None);
+    assert_eq!(Id::decode(&[0, 0x81, 0x01]), Some((Id::Num(129), 3)));
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "serde-json-core")]
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use json_rpc_types::{Id, Version, Error, ErrorCode, MaxSize};
+
+type StrBuf = json_rpc_types::str_buf::StrBuf<32>;
+type Request = json_rpc_types::Request<(u8, [i32; 2]), StrBuf>;
+type Response = json_rpc_types::Response<Option<i64>, u32>;
+
+const REQUEST_SIZE: usize = <Request as MaxSize>::MAX_SIZE;
+const RESPONSE_SIZE: usize = <Response as MaxSize>::MAX_SIZE;
+
+fn worst_str<const N: usize>() -> json_rpc_types::str_buf::StrBuf<N> {
+    let mut text = json_rpc_types::str_buf::StrBuf::new();
+    while text.remaining() > 0 {
+        text.push_str("\u{1}");
+    }
+    text
+}
+
+#[test]
+fn max_size_should_fit_worst_case() {
+    let request = Request {
+        jsonrpc: Version::V2,
+        method: worst_str(),
+        params: Some((u8::MAX, [i32::MIN, i32::MIN])),
+        id: Some(Id::Str(worst_str())),
+    };
+    let mut buffer = [0u8; REQUEST_SIZE];
+    let len = request.to_slice(&mut buffer).unwrap();
+    assert_eq!(len, REQUEST_SIZE);
+
+    let error = Error {
+        code: ErrorCode::ServerError(i64::MIN),
+        message: worst_str(),
+        data: Some(u32::MAX),
+    };
+    let mut buffer = [0u8; RESPONSE_SIZE];
+    for version in [Version::V1, Version::V2].iter() {
+        let response = Response::error(*version, error.clone(), Some(Id::Str(worst_str())));
+        let len = response.to_slice(&mut buffer).unwrap();
+        assert!(len <= RESPONSE_SIZE);
+
+        let response = Response::result(*version, Some(i64::MIN), Some(Id::Str(worst_str())));
+        let len = response.to_slice(&mut buffer).unwrap();
+        assert!(len <= RESPONSE_SIZE);
+    }
+
+    let request = Request::new(StrBuf::from_str("update")).set_version(Version::V1);
+    let mut buffer = [0u8; REQUEST_SIZE];
+    let len = request.to_slice(&mut buffer).unwrap();
+    assert_eq!(&buffer[..len], br#"{"method":"update","params":[],"id":null}"#);
+}
+
+#[test]
+fn to_slice_should_fail_on_small_buffer() {
+    let request = Request::new(StrBuf::from_str("update"));
+    let mut buffer = [0u8; 16];
+    assert!(request.to_slice(&mut buffer).is_err());
+}
+
+#[test]
+fn from_slice_should_deserialize() {
+    let text = br#"{"jsonrpc":"2.0","method":"update","params":[1,[2,3]]} "#;
+    let (request, len) = Request::from_slice(text).unwrap();
+    assert_eq!(len, text.len());
+    assert_eq!(request.method, "update");
+    assert_eq!(request.params, Some((1, [2, 3])));
+    assert!(request.is_notification());
+
+    let text = br#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found","data":1},"id":null}"#;
+    let (response, _) = Response::from_slice(text).unwrap();
+    assert_eq!(response, Response::error(Version::V2, Error::from_code(ErrorCode::MethodNotFound).set_data(1), None));
+
+    let text = br#"{"jsonrpc":"2.0","result":-1,"id":null}"#;
+    let (response, _) = Response::from_slice(text).unwrap();
+    assert_eq!(response.payload, Ok(Some(-1)));
+}
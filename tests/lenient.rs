@@ -0,0 +1,67 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+type Request = json_rpc_types::Lenient<json_rpc_types::Request<Vec<serde_json::Value>>>;
+type Response<C = json_rpc_types::PreferError> = json_rpc_types::Lenient<json_rpc_types::Response<serde_json::Value, serde_json::Value>, C>;
+
+use core::convert::TryInto;
+use json_rpc_types::{Id, Version, Error, ErrorCode, PreferResult, RejectConflict};
+
+fn create_error() -> Error<serde_json::Value> {
+    Error::from_code(ErrorCode::InternalError)
+}
+
+#[test]
+fn lenient_request_should_ignore_unknown_fields() {
+    let text = r#"{"Method":"update","params":[1,2],"id":1,"usIn":1600000000,"testnet":true}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+
+    let expected = json_rpc_types::Request {
+        jsonrpc: Version::V2,
+        method: "update".try_into().unwrap(),
+        params: Some(vec![serde_json::Value::from(1), serde_json::Value::from(2)]),
+        id: Some(Id::Num(1)),
+    };
+    assert_eq!(request.into_inner(), expected);
+
+    let text = r#"{"params":[1,2],"id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `method` at line 1 column 23");
+}
+
+#[test]
+fn lenient_response_should_ignore_unknown_fields() {
+    let expected = json_rpc_types::Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(1)));
+    let text = r#"{"result":1,"id":1,"usIn":1600000000,"usOut":1600000001,"testnet":true}"#;
+    let response: Response = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), expected);
+}
+
+#[test]
+fn lenient_response_should_resolve_conflict() {
+    let text = r#"{"jsonrpc":"2.0","result":null,"error":{"code":-32603,"message":"Internal error"},"id":1}"#;
+    let response: Response<RejectConflict> = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), json_rpc_types::Response::error(Version::V2, create_error(), Some(Id::Num(1))));
+
+    let text = r#"{"jsonrpc":"2.0","result":1,"error":{"code":-32603,"message":"Internal error"},"id":1}"#;
+    let response: Response = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), json_rpc_types::Response::error(Version::V2, create_error(), Some(Id::Num(1))));
+
+    let response: Response<PreferResult> = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), json_rpc_types::Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(1))));
+
+    let error = serde_json::from_str::<Response<RejectConflict>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC Response contains both result and error field at line 1 column 86");
+}
+
+#[test]
+fn lenient_unit_response_should_prefer_error() {
+    type NullResponse = json_rpc_types::Lenient<json_rpc_types::Response<(), serde_json::Value>, PreferResult>;
+
+    let text = r#"{"result":null,"error":{"code":-32603,"message":"Internal error"},"id":1}"#;
+    let response: NullResponse = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), json_rpc_types::Response::error(Version::V2, create_error(), Some(Id::Num(1))));
+
+    let text = r#"{"result":null,"error":null,"id":1}"#;
+    let response: NullResponse = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), json_rpc_types::Response::result(Version::V2, (), Some(Id::Num(1))));
+}
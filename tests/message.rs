@@ -0,0 +1,108 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+type Message = json_rpc_types::Message<serde_json::Value, serde_json::Value, serde_json::Value>;
+
+use core::convert::TryInto;
+use json_rpc_types::{Id, Version, Request, Response, Legacy};
+
+#[test]
+fn message_should_detect_request_version() {
+    let text = r#"{"jsonrpc":"2.0","method":"update","params":[1],"id":1}"#;
+    let message: Message = serde_json::from_str(text).unwrap();
+    assert!(matches!(message, Message::Request(_)));
+    assert_eq!(message.version(), Version::V2);
+    assert_eq!(message.id(), Some(&Id::Num(1)));
+
+    let text = r#"{"version":"1.1","method":"update","params":[1],"id":1}"#;
+    let Legacy(message) = serde_json::from_str::<Legacy<Message>>(text).unwrap();
+    assert_eq!(message.version(), Version::V1_1);
+    serde_json::from_str::<Message>(text).unwrap_err();
+
+    let text = r#"{"method":"update","params":[1],"id":1}"#;
+    let message: Message = serde_json::from_str(text).unwrap();
+    assert_eq!(message.version(), Version::V2);
+    let Legacy(message) = serde_json::from_str::<Legacy<Message>>(text).unwrap();
+    assert_eq!(message.version(), Version::V1);
+    assert_eq!(serde_json::to_string(&message).unwrap(), text);
+
+    let text = r#"{"method":"update","params":[1]}"#;
+    let message: Message = serde_json::from_str(text).unwrap();
+    assert_eq!(message.version(), Version::V2);
+    assert_eq!(message.id(), None);
+}
+
+#[test]
+fn message_should_detect_response_version() {
+    let text = r#"{"jsonrpc":"2.0","result":1,"id":1}"#;
+    let message: Message = serde_json::from_str(text).unwrap();
+    assert!(matches!(message, Message::Response(_)));
+    assert_eq!(message.version(), Version::V2);
+
+    let text = r#"{"version":"1.1","result":1,"id":1}"#;
+    let Legacy(message) = serde_json::from_str::<Legacy<Message>>(text).unwrap();
+    assert_eq!(message.version(), Version::V1_1);
+    assert_eq!(serde_json::to_string(&message).unwrap(), text);
+    serde_json::from_str::<Message>(text).unwrap_err();
+
+    let text = r#"{"result":1,"error":null,"id":1}"#;
+    let message: Message = serde_json::from_str(text).unwrap();
+    assert_eq!(message.version(), Version::V2);
+    let Legacy(message) = serde_json::from_str::<Legacy<Message>>(text).unwrap();
+    assert_eq!(message.version(), Version::V1);
+    assert_eq!(message, Message::Response(Response::result(Version::V1, serde_json::Value::from(1), Some(Id::Num(1)))));
+}
+
+#[test]
+fn message_should_fail_on_ambiguous_shape() {
+    let text = r#"{"jsonrpc":"2.0","method":"update","result":1,"id":1}"#;
+    let error = serde_json::from_str::<Message>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC message contains both request and response fields at line 1 column 53");
+
+    let text = r#"{"jsonrpc":"2.0","id":1}"#;
+    let error = serde_json::from_str::<Message>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC message is missing method, result or error field at line 1 column 24");
+
+    let text = r#"{"jsonrpc":"2.0","method":"update","trace":1}"#;
+    let error = serde_json::from_str::<Message>(text).unwrap_err();
+    assert_eq!(error.to_string(), "unknown field `trace`, expected one of `jsonrpc`, `version`, `method`, `params`, `result`, `error`, `id` at line 1 column 42");
+}
+
+#[test]
+fn v1_1_serialize_deserialize() {
+    let request = Request::<Vec<u8>> {
+        jsonrpc: Version::V1_1,
+        method: "update".try_into().unwrap(),
+        params: Some(vec![1]),
+        id: Some(Id::Num(1)),
+    };
+    let text = serde_json::to_string(&request).unwrap();
+    assert_eq!(text, r#"{"version":"1.1","method":"update","params":[1],"id":1}"#);
+    assert_eq!(serde_json::from_str::<Legacy<Request<Vec<u8>>>>(&text).unwrap().into_inner(), request);
+
+    let response = Response::<u8, ()>::result(Version::V1_1, 1, Some(Id::Num(1)));
+    let text = serde_json::to_string(&response).unwrap();
+    assert_eq!(text, r#"{"version":"1.1","result":1,"id":1}"#);
+    assert_eq!(serde_json::from_str::<Legacy<Response<u8, ()>>>(&text).unwrap().into_inner(), response);
+}
+
+#[test]
+fn v1_1_should_require_legacy() {
+    let text = r#"{"version":"1.1","method":"update","params":[1],"id":1}"#;
+    let error = serde_json::from_str::<Request<Vec<u8>>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPCv1.1 `version` member is accepted only within Legacy at line 1 column 10");
+
+    let text = r#"{"version":"1.1","result":1,"id":1}"#;
+    let error = serde_json::from_str::<Response<u8, ()>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPCv1.1 `version` member is accepted only within Legacy at line 1 column 10");
+}
+
+#[test]
+fn v1_null_result_with_error_should_require_legacy() {
+    let text = r#"{"result":null,"error":{"code":-32601,"message":"Method not found"},"id":1}"#;
+    let error = serde_json::from_str::<Response<(), ()>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC Response contains both result and error field at line 1 column 75");
+
+    let Legacy(response) = serde_json::from_str::<Legacy<Response<(), ()>>>(text).unwrap();
+    assert_eq!(response.jsonrpc, Version::V1);
+    assert!(response.payload.is_err());
+}
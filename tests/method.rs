@@ -0,0 +1,78 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use json_rpc_types::{Id, Version, Method, MethodName, Error, ErrorCode};
+
+struct Subtract;
+
+impl Method for Subtract {
+    const NAME: &'static str = "subtract";
+    type Params = [i64; 2];
+    type Result = i64;
+    type Error = ();
+}
+
+#[test]
+fn method_request_round_trip() {
+    let request = Subtract::request([42, 23], Id::Num(1));
+    let text = serde_json::to_string(&request).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","method":"subtract","params":[42,23],"id":1}"#);
+
+    let mut der = serde_json::Deserializer::from_str(&text);
+    let decoded = Subtract::decode_request(&mut der).unwrap();
+    assert!(Subtract::matches(&decoded));
+    assert_eq!(decoded.params, Some([42, 23]));
+    assert_eq!(decoded.id, Some(Id::Num(1)));
+
+    let notification = Subtract::notification([1, 2]);
+    assert!(notification.is_notification());
+    let text = serde_json::to_string(&notification).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","method":"subtract","params":[1,2]}"#);
+}
+
+#[test]
+fn method_decode_request_should_fail_on_other_method() {
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":[42,23],"id":1}"#;
+    let mut der = serde_json::Deserializer::from_str(text);
+    let error = Subtract::decode_request(&mut der).unwrap_err();
+    assert_eq!(error.to_string(), "invalid value: string \"add\", expected subtract");
+}
+
+#[test]
+fn method_response_round_trip() {
+    let response = Subtract::result(19, Some(Id::Num(1)));
+    let text = serde_json::to_string(&response).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","result":19,"id":1}"#);
+    let mut der = serde_json::Deserializer::from_str(&text);
+    assert_eq!(Subtract::decode_response(&mut der).unwrap(), response);
+
+    let response = Subtract::error(Error::from_code(ErrorCode::InvalidParams), Some(Id::Num(1)));
+    let text = serde_json::to_string(&response).unwrap();
+    let mut der = serde_json::Deserializer::from_str(&text);
+    let decoded = Subtract::decode_response(&mut der).unwrap();
+    assert_eq!(decoded.jsonrpc, Version::V2);
+    assert_eq!(decoded, response);
+}
+
+#[test]
+fn method_name_should_tolerate_overlong_name() {
+    type Request = json_rpc_types::Request<[i64; 2], MethodName>;
+    type Response = json_rpc_types::Response<(), ()>;
+
+    let text = r#"{"jsonrpc":"2.0","method":"subtract","params":[42,23],"id":1}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert_eq!(request.method.as_str(), Some("subtract"));
+    assert_eq!(serde_json::to_string(&request).unwrap(), text);
+    let request = request.into_method::<(), ()>().unwrap();
+    assert_eq!(request.method, "subtract");
+
+    let text = r#"{"jsonrpc":"2.0","method":"subtract_subtract_subtract_subtract","params":[42,23],"id":1}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert_eq!(request.method, MethodName::Overlong);
+    assert!(serde_json::to_string(&request).is_err());
+    let response = request.into_method::<(), ()>().unwrap_err().unwrap();
+    assert_eq!(response, Response::error(Version::V2, Error::from_code(ErrorCode::MethodNotFound), Some(Id::Num(1))));
+
+    let text = r#"{"jsonrpc":"2.0","method":"subtract_subtract_subtract_subtract","params":[42,23]}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert!(request.into_method::<(), ()>().unwrap_err().is_none());
+}
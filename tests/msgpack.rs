@@ -0,0 +1,121 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+#![cfg(feature = "msgpack")]
+
+use json_rpc_types::{Id, Version, Error, ErrorCode, Request, Response, Message, MsgPackRpc};
+use json_rpc_types::str_buf::StrBuf;
+
+use core::convert::TryFrom;
+use rmpv::Value;
+
+type Rpc = MsgPackRpc<(u32, u32), u32, ()>;
+
+fn encode(message: &Rpc) -> Value {
+    let bytes = rmp_serde::to_vec_named(message).expect("To serialize");
+    rmpv::decode::read_value(&mut &bytes[..]).expect("To decode")
+}
+
+fn decode(value: &Value) -> Rpc {
+    let mut bytes = Vec::new();
+    rmpv::encode::write_value(&mut bytes, value).expect("To encode");
+    rmp_serde::from_slice(&bytes).expect("To deserialize")
+}
+
+fn round_trip(message: Rpc) {
+    assert_eq!(decode(&encode(&message)), message);
+}
+
+#[test]
+fn msgpack_should_encode_messages_as_arrays() {
+    let request = Rpc::Request { msgid: 1, method: StrBuf::from_str("add"), params: Some((1, 2)) };
+    assert_eq!(encode(&request), Value::Array(vec![0.into(), 1.into(), "add".into(), Value::Array(vec![1.into(), 2.into()])]));
+    round_trip(request);
+
+    let notification = Rpc::Notification { method: StrBuf::from_str("ping"), params: None };
+    assert_eq!(encode(&notification), Value::Array(vec![2.into(), "ping".into(), Value::Array(vec![])]));
+
+    let response = Rpc::Response { msgid: 1, payload: Ok(3) };
+    assert_eq!(encode(&response), Value::Array(vec![1.into(), 1.into(), Value::Nil, 3.into()]));
+    round_trip(response);
+
+    let response = Rpc::Response { msgid: 2, payload: Err(Error::from_code(ErrorCode::MethodNotFound)) };
+    assert_eq!(encode(&response), Value::Array(vec![1.into(), 2.into(), Value::Map(vec![
+        ("code".into(), (-32601).into()),
+        ("message".into(), "Method not found".into()),
+    ]), Value::Nil]));
+    round_trip(response);
+}
+
+#[test]
+fn msgpack_should_reject_invalid_messages() {
+    let invalid = [
+        Value::Array(vec![3.into(), "ping".into(), Value::Array(vec![])]),
+        Value::Array(vec![0.into(), 1.into(), "add".into()]),
+        Value::Array(vec![2.into(), "ping".into(), Value::Array(vec![]), Value::Nil]),
+        Value::Map(vec![]),
+    ];
+
+    for value in invalid.iter() {
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, value).unwrap();
+        assert!(rmp_serde::from_slice::<Rpc>(&bytes).is_err(), "{:?} should be rejected", value);
+    }
+}
+
+#[test]
+fn msgpack_should_bridge_with_json_rpc() {
+    let request = Request::<(u32, u32)>::new(StrBuf::from_str("add")).set_params((1, 2)).set_id(7);
+    let message = Rpc::try_from(request.clone()).unwrap();
+    assert_eq!(message, Rpc::Request { msgid: 7, method: StrBuf::from_str("add"), params: Some((1, 2)) });
+    assert_eq!(Message::from(message), Message::Request(request));
+
+    let notification = Request::<(u32, u32)>::new(StrBuf::from_str("ping"));
+    let message = Rpc::try_from(notification.clone()).unwrap();
+    assert_eq!(message.msgid(), None);
+    assert_eq!(Message::from(message), Message::Request(notification));
+
+    let response = Response::<u32, ()>::result(Version::V2, 3, Some(Id::Num(7)));
+    let message = Rpc::try_from(response.clone()).unwrap();
+    assert_eq!(message, Rpc::Response { msgid: 7, payload: Ok(3) });
+    assert_eq!(Message::from(message), Message::Response(response));
+
+    let message = Message::<(u32, u32), u32, ()>::Response(Response::result(Version::V2, 3, Some(Id::Num(7))));
+    assert_eq!(Rpc::try_from(message).unwrap().msgid(), Some(7));
+}
+
+#[test]
+fn msgpack_should_not_bridge_incompatible_id() {
+    let request = Request::<(u32, u32)>::new(StrBuf::from_str("add")).set_id(Id::Str(StrBuf::from_str("id")));
+    assert_eq!(Rpc::try_from(request.clone()), Err(request));
+
+    let request = Request::<(u32, u32)>::new(StrBuf::from_str("add")).set_id(u64::from(u32::MAX) + 1);
+    assert_eq!(Rpc::try_from(request.clone()), Err(request));
+
+    let response = Response::<u32, ()>::error(Version::V2, Error::from_code(ErrorCode::ParseError), None);
+    assert_eq!(Rpc::try_from(response.clone()), Err(response));
+}
+
+#[test]
+fn msgpack_should_accept_any_error() {
+    fn error(code: ErrorCode, message: &str) -> Error<()> {
+        Error::try_with_text_message(code, message).unwrap()
+    }
+
+    //Neovim replies with `[type, message]`.
+    let response = decode(&Value::Array(vec![1.into(), 1.into(), Value::Array(vec![0.into(), "Invalid method: foo".into()]), Value::Nil]));
+    assert_eq!(response, Rpc::Response { msgid: 1, payload: Err(error(ErrorCode::ServerError(0), "Invalid method: foo")) });
+
+    let response = decode(&Value::Array(vec![1.into(), 2.into(), "Failure".into(), Value::Nil]));
+    assert_eq!(response, Rpc::Response { msgid: 2, payload: Err(error(ErrorCode::ServerError(-32000), "Failure")) });
+
+    let response = decode(&Value::Array(vec![1.into(), 3.into(), Value::Array(vec!["Failure".into()]), Value::Nil]));
+    assert_eq!(response, Rpc::Response { msgid: 3, payload: Err(error(ErrorCode::ServerError(-32000), "Server error")) });
+
+    let response = decode(&Value::Array(vec![1.into(), 4.into(), (-32602).into(), Value::Nil]));
+    assert_eq!(response, Rpc::Response { msgid: 4, payload: Err(error(ErrorCode::ServerError(-32000), "Server error")) });
+
+    let response = decode(&Value::Array(vec![1.into(), 5.into(), Value::Array(vec![(-32601).into(), Value::Nil, "extra".into()]), Value::Nil]));
+    assert_eq!(response, Rpc::Response { msgid: 5, payload: Err(Error::from_code(ErrorCode::MethodNotFound)) });
+
+    let response = decode(&Value::Array(vec![1.into(), 6.into(), Value::Nil, 3.into()]));
+    assert_eq!(response, Rpc::Response { msgid: 6, payload: Ok(3) });
+}
@@ -0,0 +1,153 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use json_rpc_types::Params;
+
+type Request = json_rpc_types::Request<Params<Vec<serde_json::Value>, Args>>;
+
+#[derive(Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+struct Args {
+    a: i32,
+    b: Option<i32>,
+}
+
+const ARGS: &[&str] = &["a", "b"];
+
+#[test]
+fn params_deserialize() {
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":[1,2],"id":1}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    let params = request.params.unwrap();
+    assert!(params.is_positional());
+    assert_eq!(params, Params::Positional(vec![serde_json::Value::from(1), serde_json::Value::from(2)]));
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":{"a":1,"b":2},"id":1}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    let params = request.params.unwrap();
+    assert!(!params.is_positional());
+    assert_eq!(params, Params::Named(Args { a: 1, b: Some(2) }));
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":null,"id":1}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+    assert_eq!(request.params, None);
+}
+
+#[test]
+fn params_should_reject_scalar() {
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":1,"id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: integer `1`, expected Invalid params: must be either array or object at line 1 column 42");
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":"a","id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: string \"a\", expected Invalid params: must be either array or object at line 1 column 44");
+}
+
+#[test]
+fn params_serialize() {
+    let params: Params<Vec<i32>, Args> = Params::Positional(vec![1, 2]);
+    assert_eq!(serde_json::to_string(&params).unwrap(), "[1,2]");
+
+    let params: Params<Vec<i32>, Args> = Params::Named(Args { a: 1, b: None });
+    assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"a":1,"b":null}"#);
+}
+
+#[test]
+fn params_positional_into_named() {
+    let params: Params<Vec<serde_json::Value>, Args> = Params::Positional(vec![serde_json::Value::from(1), serde_json::Value::from(2)]);
+    let args = params.into_named::<_, serde_json::Error>(ARGS).unwrap();
+    assert_eq!(args, Args { a: 1, b: Some(2) });
+
+    let params: Params<Vec<serde_json::Value>, Args> = Params::Positional(vec![serde_json::Value::from(1)]);
+    let args = params.into_named::<_, serde_json::Error>(ARGS).unwrap();
+    assert_eq!(args, Args { a: 1, b: None });
+
+    let params: Params<Vec<serde_json::Value>, Args> = Params::Named(Args { a: 1, b: None });
+    let args = params.into_named::<_, serde_json::Error>(ARGS).unwrap();
+    assert_eq!(args, Args { a: 1, b: None });
+
+    let params: Params<Vec<serde_json::Value>, Args> = Params::Positional(vec![]);
+    let error = params.into_named::<_, serde_json::Error>(ARGS).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `a`");
+
+    let params: Params<Vec<serde_json::Value>, Args> = Params::Positional(vec![serde_json::Value::from(1), serde_json::Value::from(2), serde_json::Value::from(3)]);
+    let error = params.into_named::<_, serde_json::Error>(ARGS).unwrap_err();
+    assert_eq!(error.to_string(), "Invalid params: expected at most 2 parameters");
+}
+
+#[derive(Debug, PartialEq, serde_derive::Deserialize)]
+struct AddArgs {
+    a: i32,
+    b: i32,
+    scale: Option<u8>,
+}
+
+type ArgsRequest = json_rpc_types::Request<json_rpc_types::Args<AddArgs>>;
+
+#[test]
+fn args_deserialize_positional_and_named() {
+    let expected = AddArgs { a: 1, b: 2, scale: None };
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":[1,2],"id":1}"#;
+    let request: ArgsRequest = serde_json::from_str(text).unwrap();
+    assert_eq!(request.params.unwrap().into_inner(), expected);
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":[1,2,null],"id":1}"#;
+    let request: ArgsRequest = serde_json::from_str(text).unwrap();
+    assert_eq!(request.params.unwrap().into_inner(), expected);
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":{"b":2,"a":1},"id":1}"#;
+    let request: ArgsRequest = serde_json::from_str(text).unwrap();
+    assert_eq!(request.params.unwrap().into_inner(), expected);
+
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":[1,2,3],"id":1}"#;
+    let request: ArgsRequest = serde_json::from_str(text).unwrap();
+    assert_eq!(request.params.unwrap().into_inner(), AddArgs { a: 1, b: 2, scale: Some(3) });
+}
+
+#[test]
+fn args_should_report_failed_argument() {
+    let text = r#"[1,"2"]"#;
+    let error = serde_json::from_str::<json_rpc_types::Args<AddArgs>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "Invalid argument #2 'b': invalid type: string \"2\", expected i32 at line 1 column 6");
+
+    let text = r#"[1]"#;
+    let error = serde_json::from_str::<json_rpc_types::Args<AddArgs>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "Missing argument #2 'b' at line 1 column 3");
+
+    let text = r#"[1,2,3,4]"#;
+    let error = serde_json::from_str::<json_rpc_types::Args<AddArgs>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "Invalid params: expected at most 3 arguments at line 1 column 9");
+
+    let text = r#"1"#;
+    let error = serde_json::from_str::<json_rpc_types::Args<AddArgs>>(text).unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: integer `1`, expected array or object of arguments at line 1 column 1");
+}
+
+#[test]
+fn args_deserialize_tuple() {
+    type Tuple = json_rpc_types::Args<(i32, Option<i32>)>;
+
+    let args: Tuple = serde_json::from_str("[1,2]").unwrap();
+    assert_eq!(args.into_inner(), (1, Some(2)));
+
+    let args: Tuple = serde_json::from_str("[1]").unwrap();
+    assert_eq!(args.into_inner(), (1, None));
+
+    let error = serde_json::from_str::<Tuple>("[]").unwrap_err();
+    assert_eq!(error.to_string(), "Missing argument #1 at line 1 column 2");
+
+    let error = serde_json::from_str::<Tuple>("[1,2,3]").unwrap_err();
+    assert_eq!(error.to_string(), "Invalid params: expected at most 2 arguments at line 1 column 7");
+}
+
+#[test]
+fn args_deserialize_with() {
+    #[derive(Debug, PartialEq, serde_derive::Deserialize)]
+    struct Call {
+        #[serde(deserialize_with = "json_rpc_types::deserialize_args")]
+        params: AddArgs,
+    }
+
+    let call: Call = serde_json::from_str(r#"{"params":[1,2,3]}"#).unwrap();
+    assert_eq!(call.params, AddArgs { a: 1, b: 2, scale: Some(3) });
+}
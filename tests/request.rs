@@ -3,7 +3,7 @@
 type Request = json_rpc_types::Request<Vec<serde_json::Value>>;
 
 use core::convert::TryInto;
-use json_rpc_types::{Id, Version};
+use json_rpc_types::{Id, Version, Error, ErrorCode, Legacy};
 
 #[test]
 fn method_call_serialize() {
@@ -101,3 +101,100 @@ fn call_deserialize() {
     assert!(!notification.is_notification());
     assert_eq!(expected, notification);
 }
+
+#[test]
+fn v1_request_serialize() {
+    let m = Request {
+        jsonrpc: Version::V1,
+        method: "getblockcount".try_into().unwrap(),
+        params: None,
+        id: Some(Id::Num(1)),
+    };
+
+    let serialized = serde_json::to_string(&m).unwrap();
+    assert_eq!(serialized, r#"{"method":"getblockcount","params":[],"id":1}"#);
+
+    let m = Request {
+        jsonrpc: Version::V1,
+        method: "update".try_into().unwrap(),
+        params: Some(vec![serde_json::Value::from(1), serde_json::Value::from(2)]),
+        id: None,
+    };
+
+    let serialized = serde_json::to_string(&m).unwrap();
+    assert_eq!(serialized, r#"{"method":"update","params":[1,2],"id":null}"#);
+}
+
+#[test]
+fn v1_request_deserialize() {
+    let text = r#"{"method":"update","params":[1,2],"id":null}"#;
+    let Legacy(notification) = serde_json::from_str::<Legacy<Request>>(text).unwrap();
+
+    let expected = Request {
+        jsonrpc: Version::V1,
+        method: "update".try_into().unwrap(),
+        params: Some(vec![serde_json::Value::from(1), serde_json::Value::from(2)]),
+        id: None,
+    };
+
+    assert!(notification.is_notification());
+    assert_eq!(expected, notification);
+
+    let text = r#"{"method":"update","params":[],"id":"1"}"#;
+    let Legacy(call) = serde_json::from_str::<Legacy<Request>>(text).unwrap();
+
+    assert!(!call.is_notification());
+    assert_eq!(call.jsonrpc, Version::V1);
+    assert_eq!(call.params, Some(vec![]));
+
+    let text = r#"{"jsonrpc":"2.0","method":"update","id":1}"#;
+    let Legacy(call) = serde_json::from_str::<Legacy<Request>>(text).unwrap();
+    assert_eq!(call.jsonrpc, Version::V2);
+}
+
+#[test]
+fn request_without_version_should_default_to_v2() {
+    let text = r#"{"method":"update","id":1}"#;
+    let call: Request = serde_json::from_str(text).unwrap();
+    assert_eq!(call.jsonrpc, Version::V2);
+    assert_eq!(call.id, Some(Id::Num(1)));
+}
+
+#[test]
+fn request_deserialize_should_fail_on_unknown_field() {
+    let text = r#"{"jsonrpc":"2.0","method":"update","trace":"1.1"}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "unknown field `trace`, expected one of `jsonrpc`, `version`, `method`, `params`, `id` at line 1 column 42");
+}
+
+#[test]
+fn request_reply_should_copy_version_and_id() {
+    type Response = json_rpc_types::Response<u64, ()>;
+
+    let text = r#"[{"jsonrpc":"2.0","method":"sum","params":[1,2],"id":"1"},{"method":"sum","params":[3],"id":2},{"jsonrpc":"2.0","method":"notify"}]"#;
+    let batch: Vec<Legacy<Request>> = serde_json::from_str(text).unwrap();
+    let batch: Vec<Request> = batch.into_iter().map(Legacy::into_inner).collect();
+
+    let responses: Vec<Response> = batch.iter().filter_map(|request| request.reply_ok(request.params.as_ref().unwrap_or(&vec![]).len() as u64)).collect();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0], Response::result(Version::V2, 2, Some(Id::Str("1".try_into().unwrap()))));
+    assert_eq!(responses[1], Response::result(Version::V1, 1, Some(Id::Num(2))));
+
+    let response: Response = batch[1].reply_err(Error::from_code(ErrorCode::InternalError)).unwrap();
+    assert_eq!(response, Response::error(Version::V1, Error::from_code(ErrorCode::InternalError), Some(Id::Num(2))));
+
+    let response: Response = batch[0].reply_with(ErrorCode::InvalidParams).unwrap();
+    assert_eq!(response, Response::error(Version::V2, Error::from_code(ErrorCode::InvalidParams), Some(Id::Str("1".try_into().unwrap()))));
+
+    let response: Option<Response> = batch[2].reply_ok(1);
+    assert_eq!(response, None);
+    let response: Option<Response> = batch[2].reply_with(ErrorCode::InvalidParams);
+    assert_eq!(response, None);
+}
+
+#[test]
+fn request_deserialize_should_fail_on_duplicate_params_after_null() {
+    let text = r#"{"jsonrpc":"2.0","method":"update","params":null,"params":[1]}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert!(error.to_string().starts_with("duplicate field `params`"));
+}
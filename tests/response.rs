@@ -2,7 +2,7 @@
 
 type Response = json_rpc_types::Response<serde_json::Value, serde_json::Value>;
 
-use json_rpc_types::{Id, Version, Error, ErrorCode};
+use json_rpc_types::{Id, Version, Error, ErrorCode, ClientError, Legacy};
 
 fn create_error() -> Error<serde_json::Value> {
     let mut error = Error::from_code(ErrorCode::MethodNotFound);
@@ -110,3 +110,127 @@ fn success_deserialize_from_serde_json() {
     let response3: NullResponse = serde_json::from_value(value).unwrap();
     println!("{:?}", response3);
 }
+
+#[test]
+fn v1_response_serialize() {
+    let result = Response::result(Version::V1, serde_json::Value::from(1), Some(Id::Num(1)));
+    let serialized = serde_json::to_string(&result).unwrap();
+    assert_eq!(serialized, r#"{"result":1,"error":null,"id":1}"#);
+
+    let result = Response::error(Version::V1, create_error(), Some(Id::Num(1)));
+    let serialized = serde_json::to_string(&result).unwrap();
+    assert_eq!(serialized, r#"{"result":null,"error":{"code":-32601,"message":"Method not found","data":"text"},"id":1}"#);
+}
+
+#[test]
+fn v1_response_deserialize() {
+    let expected = Response::result(Version::V1, serde_json::Value::from(1), Some(Id::Num(1)));
+    let text = r#"{"result":1,"error":null,"id":1}"#;
+    let Legacy(result) = serde_json::from_str::<Legacy<Response>>(text).unwrap();
+    assert_eq!(result, expected);
+
+    let expected = Response::error(Version::V1, create_error(), Some(Id::Num(1)));
+    let text = r#"{"result":null,"error":{"code":-32601,"message":"Method not found","data":"text"},"id":1}"#;
+    let Legacy(result) = serde_json::from_str::<Legacy<Response>>(text).unwrap();
+    assert_eq!(result, expected);
+
+    let expected = Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(1)));
+    let text = r#"{"result":1,"id":1}"#;
+    let Legacy(result) = serde_json::from_str::<Legacy<Response>>(text).unwrap();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn response_without_version_should_default_to_v2() {
+    let expected = Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(1)));
+    let text = r#"{"result":1,"error":null,"id":1}"#;
+    let result: Response = serde_json::from_str(text).unwrap();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn v1_null_response_deserialize() {
+    type NullResponse = json_rpc_types::Response<(), serde_json::Value>;
+
+    let expected = NullResponse::error(Version::V1, create_error(), Some(Id::Num(1)));
+    let text = r#"{"result":null,"error":{"code":-32601,"message":"Method not found","data":"text"},"id":1}"#;
+    let Legacy(result) = serde_json::from_str::<Legacy<NullResponse>>(text).unwrap();
+    assert_eq!(result, expected);
+
+    let text = r#"{"error":{"code":-32601,"message":"Method not found","data":"text"},"result":null,"id":1}"#;
+    let Legacy(result) = serde_json::from_str::<Legacy<NullResponse>>(text).unwrap();
+    assert_eq!(result, expected);
+
+    let text = r#"{"result":null,"error":null,"id":1}"#;
+    let Legacy(result) = serde_json::from_str::<Legacy<NullResponse>>(text).unwrap();
+    assert_eq!(result, NullResponse::result(Version::V1, (), Some(Id::Num(1))));
+
+    let text = r#"{"result":null,"error":{"code":-32601,"message":"Method not found","data":"text"},"id":1,"jsonrpc":"2.0"}"#;
+    let error = serde_json::from_str::<NullResponse>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC Response contains both result and error field at line 1 column 105");
+}
+
+#[test]
+fn response_combinators() {
+    let response = Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(1)));
+    assert!(response.is_success());
+    assert_eq!(response.as_result(), Ok(&serde_json::Value::from(1)));
+    let response = response.map_result(|value| value.as_u64().unwrap() + 1);
+    assert_eq!(response.payload, Ok(2));
+    assert_eq!(response.id, Some(Id::Num(1)));
+    assert_eq!(response.into_result(), Ok(2));
+
+    let response = Response::error(Version::V2, create_error(), Some(Id::Num(1)));
+    assert!(!response.is_success());
+    assert_eq!(response.as_result(), Err(&create_error()));
+    let response = response.map_error_data(|data| data.as_str().unwrap().len());
+    let error = response.into_result().unwrap_err();
+    assert_eq!(error.code, ErrorCode::MethodNotFound);
+    assert_eq!(error.message, "Method not found");
+    assert_eq!(error.data, Some(4));
+}
+
+#[test]
+fn response_into_client_result() {
+    fn call(response: Response) -> Result<u64, ClientError<serde_json::Value>> {
+        let value = response.into_client_result(&Id::Num(1))?;
+        Ok(value.as_u64().unwrap())
+    }
+
+    assert_eq!(call(Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(1)))), Ok(1));
+
+    let error = call(Response::error(Version::V2, create_error(), Some(Id::Num(1)))).unwrap_err();
+    assert_eq!(error, ClientError::Rpc(create_error()));
+    assert_eq!(error.to_string(), "Method not found");
+
+    let error = call(Response::result(Version::V2, serde_json::Value::from(1), Some(Id::Num(2)))).unwrap_err();
+    assert_eq!(error, ClientError::IdMismatch { expected: Id::Num(1), actual: Some(Id::Num(2)) });
+    let error = call(Response::error(Version::V2, create_error(), None)).unwrap_err();
+    assert_eq!(error, ClientError::IdMismatch { expected: Id::Num(1), actual: None });
+
+    let error = call(Response::error(Version::V2, Error::from_code(ErrorCode::InvalidRequest), None)).unwrap_err();
+    assert_eq!(error, ClientError::Protocol(Error::from_code(ErrorCode::InvalidRequest)));
+    assert_eq!(error.to_string(), "Protocol error: Invalid Request");
+}
+
+#[test]
+fn client_error_should_display_custom_message_type() {
+    let error: Error<(), String> = Error::with_text_message(ErrorCode::ServerError(1), "Disk is full");
+    assert_eq!(error.to_string(), "Server error");
+    assert_eq!(ClientError::Rpc(error.clone()).to_string(), "Disk is full");
+    assert_eq!(ClientError::Protocol(error).to_string(), "Protocol error: Disk is full");
+
+    let error: ClientError<(), &str> = ClientError::Rpc(Error::with_text_message(ErrorCode::InternalError, "Oops"));
+    assert_eq!(error.to_string(), "Oops");
+}
+
+#[test]
+fn response_deserialize_should_fail_on_duplicate_member_after_null() {
+    let text = r#"{"jsonrpc":"2.0","result":null,"result":1,"id":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert!(error.to_string().starts_with("duplicate field `result`"));
+
+    let text = r#"{"jsonrpc":"2.0","error":null,"error":{"code":-32601,"message":"Method not found"},"id":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert!(error.to_string().starts_with("duplicate field `error`"));
+}
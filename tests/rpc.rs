@@ -0,0 +1,154 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+use core::convert::TryInto;
+use json_rpc_types::{Dispatch, Error, ErrorCode, Id, Method};
+use json_rpc_types_derive::rpc;
+
+#[rpc]
+pub trait Calc {
+    fn subtract(&self, minuend: i64, subtrahend: i64) -> Result<i64, Error<()>>;
+    fn sum(&self, values: Vec<i64>) -> i64;
+    fn ping(&self);
+}
+
+#[rpc]
+pub trait HTTPApi {
+    fn get(&self, path: String) -> String;
+}
+
+type Message = json_rpc_types::str_buf::StrBuf<64>;
+
+///Shares name with generated type, which must not shadow it within methods.
+#[derive(serde_derive::Serialize, serde_derive::Deserialize, Debug, PartialEq)]
+pub struct Response {
+    status: u16,
+}
+
+#[rpc]
+pub trait Status {
+    fn status(&self, code: u16) -> Result<Response, Error<u16, Message>>;
+}
+
+struct Server;
+
+impl Calc for Server {
+    fn subtract(&self, minuend: i64, subtrahend: i64) -> Result<i64, Error<()>> {
+        minuend.checked_sub(subtrahend).ok_or_else(|| Error::from_code(ErrorCode::InvalidParams))
+    }
+
+    fn sum(&self, values: Vec<i64>) -> i64 {
+        values.iter().sum()
+    }
+
+    fn ping(&self) {
+    }
+}
+
+impl HTTPApi for Server {
+    fn get(&self, path: String) -> String {
+        path
+    }
+}
+
+impl Status for Server {
+    fn status(&self, code: u16) -> Result<Response, Error<u16, Message>> {
+        match code {
+            200 => Ok(Response { status: code }),
+            _ => Err(Error::with_custom_msg(ErrorCode::ServerError(1), "Status code is not supported by this server").set_data(code)),
+        }
+    }
+}
+
+fn call(text: &str) -> Option<String> {
+    let request: Dispatch<calc::Request> = serde_json::from_str(text).unwrap();
+    calc::dispatch(&Server, request).map(|response| serde_json::to_string(&response).unwrap())
+}
+
+#[test]
+fn rpc_client_should_build_requests() {
+    let request = calc::Subtract::request((42, 23), Id::Num(1));
+    let text = serde_json::to_string(&request).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","method":"subtract","params":[42,23],"id":1}"#);
+
+    let request = calc::Ping::notification([]);
+    let text = serde_json::to_string(&request).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","method":"ping","params":[]}"#);
+
+    let text = r#"{"jsonrpc":"2.0","result":19,"id":1}"#;
+    let mut der = serde_json::Deserializer::from_str(text);
+    let response = calc::Subtract::decode_response(&mut der).unwrap();
+    assert_eq!(response.payload, Ok(19));
+}
+
+#[test]
+fn rpc_server_should_dispatch_requests() {
+    let request = serde_json::to_string(&calc::Subtract::request((42, 23), Id::Num(1))).unwrap();
+    assert_eq!(call(&request).unwrap(), r#"{"jsonrpc":"2.0","result":19,"id":1}"#);
+
+    let request = serde_json::to_string(&calc::Subtract::request((i64::MIN, 1), Id::Num(2))).unwrap();
+    assert_eq!(call(&request).unwrap(), r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params"},"id":2}"#);
+
+    assert_eq!(call(r#"{"jsonrpc":"2.0","method":"sum","params":[[1,2,3]],"id":3}"#).unwrap(), r#"{"jsonrpc":"2.0","result":6,"id":3}"#);
+    assert_eq!(call(r#"{"jsonrpc":"2.0","method":"ping","id":4}"#).unwrap(), r#"{"jsonrpc":"2.0","result":null,"id":4}"#);
+    assert_eq!(call(r#"{"jsonrpc":"2.0","method":"ping"}"#), None);
+}
+
+#[test]
+fn rpc_server_should_reply_method_not_found() {
+    assert_eq!(call(r#"{"jsonrpc":"2.0","method":"divide","params":[1,2],"id":1}"#).unwrap(), r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":1}"#);
+    assert_eq!(call(r#"{"jsonrpc":"2.0","method":"divide","params":[1,2]}"#), None);
+}
+
+#[test]
+fn rpc_server_should_reply_invalid_params_without_params() {
+    let request = json_rpc_types::Request::<calc::Request>::new("sum".try_into().unwrap()).set_id(1);
+    let response = calc::dispatch(&Server, Dispatch::Method(request)).unwrap();
+    assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params"},"id":1}"#);
+
+    let request = json_rpc_types::Request::<calc::Request>::new("sum".try_into().unwrap());
+    assert!(calc::dispatch(&Server, Dispatch::Method(request)).is_none());
+}
+
+#[test]
+fn rpc_module_should_treat_capitals_as_word() {
+    let request = http_api::Get::request((String::from("/"),), Id::Num(1));
+    let text = serde_json::to_string(&request).unwrap();
+    assert_eq!(text, r#"{"jsonrpc":"2.0","method":"get","params":["/"],"id":1}"#);
+
+    let text = r#"{"jsonrpc":"2.0","method":"get","params":["/index"],"id":2}"#;
+    let response = http_api::dispatch(&Server, serde_json::from_str(text).unwrap()).unwrap();
+    assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"jsonrpc":"2.0","result":"/index","id":2}"#);
+}
+
+#[test]
+fn rpc_server_should_reply_invalid_params_from_json() {
+    assert_eq!(call(r#"{"jsonrpc":"2.0","method":"sum","id":1}"#).unwrap(), r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params"},"id":1}"#);
+    assert_eq!(call(r#"{"jsonrpc":"2.0","method":"sum"}"#), None);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn rpc_server_should_reply_invalid_params_from_parse_dispatch() {
+    let request = json_rpc_types::parse_dispatch::<calc::Request, _>(br#"{"jsonrpc":"2.0","method":"subtract","params":["42",23],"id":1}"#).unwrap();
+    let response = calc::dispatch(&Server, request).unwrap();
+    assert!(matches!(response, calc::Response::InvalidParams(_)));
+    assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid params"},"id":1}"#);
+
+    let request = json_rpc_types::parse_dispatch::<calc::Request, _>(br#"{"jsonrpc":"2.0","method":"subtract","params":["42",23]}"#).unwrap();
+    assert!(calc::dispatch(&Server, request).is_none());
+}
+
+#[test]
+fn rpc_should_forward_error_message_type() {
+    let text = r#"{"jsonrpc":"2.0","method":"status","params":[200],"id":1}"#;
+    let response = status::dispatch(&Server, serde_json::from_str(text).unwrap()).unwrap();
+    assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"jsonrpc":"2.0","result":{"status":200},"id":1}"#);
+
+    let text = r#"{"jsonrpc":"2.0","method":"status","params":[500],"id":2}"#;
+    let response = status::dispatch(&Server, serde_json::from_str(text).unwrap()).unwrap();
+    assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"jsonrpc":"2.0","error":{"code":1,"message":"Status code is not supported by this server","data":500},"id":2}"#);
+    match response {
+        status::Response::Status(response) => assert_eq!(response.payload.unwrap_err().message.as_str(), "Status code is not supported by this server"),
+        _ => panic!("Unexpected response"),
+    }
+}
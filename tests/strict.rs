@@ -0,0 +1,88 @@
+#![cfg_attr(feature = "cargo-fmt", rustfmt::skip)]
+
+type Request = json_rpc_types::Strict<json_rpc_types::Request<Vec<serde_json::Value>>>;
+type Response = json_rpc_types::Strict<json_rpc_types::Response<serde_json::Value, serde_json::Value>>;
+type NamedRequest = json_rpc_types::Strict<json_rpc_types::Request<serde_json::Value>>;
+
+use core::convert::TryInto;
+use json_rpc_types::{Id, Version, Error, ErrorCode};
+
+#[test]
+fn strict_request_deserialize() {
+    let text = r#"{"jsonrpc":"2.0","method":"update","params":[1,2],"id":1}"#;
+    let request: Request = serde_json::from_str(text).unwrap();
+
+    let expected = json_rpc_types::Request {
+        jsonrpc: Version::V2,
+        method: "update".try_into().unwrap(),
+        params: Some(vec![serde_json::Value::from(1), serde_json::Value::from(2)]),
+        id: Some(Id::Num(1)),
+    };
+    assert_eq!(request.into_inner(), expected);
+
+    let text = r#"{"jsonrpc":"2.0","method":"update","params":{"a":1}}"#;
+    let request: NamedRequest = serde_json::from_str(text).unwrap();
+    assert!(request.is_notification());
+    assert_eq!(request.params, Some(serde_json::json!({"a": 1})));
+}
+
+#[test]
+fn strict_request_should_fail_on_violations() {
+    let text = r#"{"method":"update","params":[1,2],"id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `jsonrpc` at line 1 column 41");
+
+    let text = r#"{"jsonrpc":"1.0","method":"update","id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "invalid value: string \"1.0\", expected 2.0 at line 1 column 16");
+
+    let text = r#"{"jsonrpc":"2.0","Method":"update","id":1}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "unknown field `Method`, expected one of `jsonrpc`, `method`, `params`, `id` at line 1 column 25");
+
+    let text = r#"{"jsonrpc":"2.0","method":"update","params":1,"id":1}"#;
+    let error = serde_json::from_str::<NamedRequest>(text).unwrap_err();
+    assert_eq!(error.to_string(), "invalid type: integer `1`, expected Params must be either array or object at line 1 column 45");
+
+    let text = r#"{"jsonrpc":"2.0","method":"update","id":1,"id":2}"#;
+    let error = serde_json::from_str::<Request>(text).unwrap_err();
+    assert_eq!(error.to_string(), "duplicate field `id` at line 1 column 46");
+}
+
+#[test]
+fn strict_response_deserialize() {
+    let text = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+    let response: Response = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), json_rpc_types::Response::result(Version::V2, serde_json::Value::Null, Some(Id::Num(1))));
+
+    let text = r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":null}"#;
+    let response: Response = serde_json::from_str(text).unwrap();
+    assert_eq!(response.into_inner(), json_rpc_types::Response::error(Version::V2, Error::from_code(ErrorCode::MethodNotFound), None));
+}
+
+#[test]
+fn strict_response_should_fail_on_violations() {
+    let text = r#"{"jsonrpc":"2.0","result":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `id` at line 1 column 28");
+
+    let text = r#"{"result":1,"id":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `jsonrpc` at line 1 column 19");
+
+    let text = r#"{"JSONRPC":"2.0","result":1,"id":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert_eq!(error.to_string(), "unknown field `JSONRPC`, expected one of `jsonrpc`, `result`, `error`, `id` at line 1 column 10");
+
+    let text = r#"{"jsonrpc":"2.0","result":null,"error":{"code":-32601,"message":"Method not found"},"id":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC Response contains both result and error field at line 1 column 38");
+
+    let text = r#"{"jsonrpc":"2.0","error":null,"result":1,"id":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert!(error.to_string().starts_with("invalid type: null, expected struct Error"));
+
+    let text = r#"{"jsonrpc":"2.0","id":1}"#;
+    let error = serde_json::from_str::<Response>(text).unwrap_err();
+    assert_eq!(error.to_string(), "JSON-RPC Response is missing either result or error field. at line 1 column 24");
+}